@@ -6,7 +6,10 @@ use tauri::{
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
     Emitter, Manager,
 };
-use tools::{VerdaccioProcess, VerdaccioRunningState};
+use tools::{
+    FileWatchers, HtpasswdLock, PrivatePackageCache, TrayTooltipTemplate, VerdaccioProcess,
+    VerdaccioRunningState,
+};
 
 #[derive(Clone, serde::Serialize)]
 struct SingleInstancePayload {
@@ -41,11 +44,75 @@ fn update_tray_icon(app: &tauri::AppHandle, running: bool) {
     }
 }
 
+/// 按缓存的模板生成托盘提示文字（不读取设置文件，可在后台轮询中频繁调用）
+fn tray_tooltip(app: &tauri::AppHandle, status: &str) -> String {
+    let port = app
+        .try_state::<VerdaccioProcess>()
+        .and_then(|process| process.port.lock().ok().map(|port| *port))
+        .unwrap_or_default();
+    app.try_state::<TrayTooltipTemplate>()
+        .map(|template| template.render(status, port))
+        .unwrap_or_default()
+}
+
+/// 更新托盘提示文字
+fn update_tray_tooltip(app: &tauri::AppHandle, tooltip: &str) {
+    if let Some(tray) = app.tray_by_id("main-tray") {
+        let _ = tray.set_tooltip(Some(tooltip));
+    }
+}
+
+/// 托盘提示中的状态文字
+fn tray_status_text(running: VerdaccioRunningState) -> &'static str {
+    match running {
+        VerdaccioRunningState::Running => "运行中",
+        VerdaccioRunningState::Starting => "启动中",
+        VerdaccioRunningState::NotRunning => "已停止",
+    }
+}
+
+/// 后台轮询 Verdaccio 进程状态的间隔
+const TRAY_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// 启动后台轮询，在窗口隐藏时服务崩溃也能让托盘图标反映实际状态
+fn start_tray_status_poll(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut last_running = None;
+        let mut last_tooltip = None;
+        loop {
+            tokio::time::sleep(TRAY_POLL_INTERVAL).await;
+            let Some(process) = app.try_state::<VerdaccioProcess>() else {
+                break;
+            };
+            // 接管的外部进程退出时没有回调，需要主动检查
+            process.refresh_attached().await;
+            let running = process.check_running();
+            // 仅在状态变化时更新，避免反复解码图标
+            if last_running != Some(running) {
+                update_tray_icon(&app, running);
+                last_running = Some(running);
+            }
+            // 模板或端口可能在状态不变时被修改，每次轮询都重新生成提示文字
+            let state = if running {
+                VerdaccioRunningState::Running
+            } else {
+                VerdaccioRunningState::NotRunning
+            };
+            let tooltip = tray_tooltip(&app, tray_status_text(state));
+            if last_tooltip.as_ref() != Some(&tooltip) {
+                update_tray_tooltip(&app, &tooltip);
+                last_tooltip = Some(tooltip);
+            }
+        }
+    });
+}
+
 /// 同步检查 Verdaccio 状态并更新托盘
 #[tauri::command]
 async fn sync_tray_status(app: tauri::AppHandle, running: VerdaccioRunningState) -> Result<(), String> {
     let is_online = running == VerdaccioRunningState::Running || running == VerdaccioRunningState::Starting;
     update_tray_icon(&app, is_online);
+    update_tray_tooltip(&app, &tray_tooltip(&app, tray_status_text(running)));
     Ok(())
 }
 
@@ -55,7 +122,7 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_autostart::init(
             tauri_plugin_autostart::MacosLauncher::LaunchAgent,
-            Some(vec!["--minimized"]),
+            Some(vec![tools::settings::AUTOSTART_MINIMIZED_ARG]),
         ))
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_process::init())
@@ -74,6 +141,10 @@ pub fn run() {
             );
         }))
         .manage(VerdaccioProcess::default())
+        .manage(HtpasswdLock::default())
+        .manage(PrivatePackageCache::default())
+        .manage(TrayTooltipTemplate::default())
+        .manage(FileWatchers::default())
         .setup(|app| {
             // 创建托盘菜单
             let show_item = MenuItem::with_id(app, "show", "显示窗口", true, None::<&str>)?;
@@ -104,6 +175,7 @@ pub fn run() {
                                 }
                             }
                         }
+                        tools::stop_watchers(app);
                         app.exit(0);
                     }
                     _ => {}
@@ -124,6 +196,28 @@ pub fn run() {
                 })
                 .build(app)?;
 
+            start_tray_status_poll(app.handle().clone());
+            tools::start_log_batch_emitter(app.handle().clone());
+
+            // 用上次使用的端口初始化进程状态，重启后状态显示与用户配置一致；同时载入托盘提示模板
+            if let Ok(settings) = tools::settings::load_app_settings() {
+                if let Ok(mut port) = app.state::<VerdaccioProcess>().port.lock() {
+                    *port = settings.default_port;
+                }
+                app.state::<TrayTooltipTemplate>().set(&settings.tray_tooltip_template);
+            }
+
+            // 监听 htpasswd、存储目录与配置文件的变更
+            if let Err(e) = tools::start_users_watcher(app.handle()) {
+                app.state::<VerdaccioProcess>().add_log("WARN", e);
+            }
+            if let Err(e) = tools::start_storage_watcher(app.handle()) {
+                app.state::<VerdaccioProcess>().add_log("WARN", e);
+            }
+            if let Err(e) = tools::start_config_watcher(app.handle()) {
+                app.state::<VerdaccioProcess>().add_log("WARN", e);
+            }
+
             Ok(())
         })
         .on_window_event(|window, event| {
@@ -136,29 +230,119 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             sync_tray_status,
             tools::start_verdaccio,
+            tools::preview_listen,
             tools::stop_verdaccio,
             tools::get_verdaccio_status,
+            tools::detect_external_verdaccio,
+            tools::attach_to_external,
+            tools::detach_from_external,
             tools::check_verdaccio_installed,
+            tools::get_setup_status,
             tools::get_verdaccio_version,
+            tools::get_running_verdaccio_version,
+            tools::get_node_version,
+            tools::get_launch_command,
+            tools::get_registry_qr,
             tools::get_verdaccio_logs,
+            tools::get_logs_since,
             tools::clear_verdaccio_logs,
+            tools::rotate_verdaccio_log,
+            tools::set_log_capture,
+            tools::get_log_level_counts,
+            tools::get_recent_requests,
+            tools::get_process_stats,
+            tools::reload_verdaccio_config,
             tools::get_verdaccio_config,
             tools::save_verdaccio_config,
             tools::get_config_file_path,
             tools::reset_config_to_default,
+            tools::apply_config_template,
+            tools::diff_config_against_default,
+            tools::open_config_in_editor,
+            tools::get_config_value,
+            tools::set_config_value,
+            tools::open_package_page,
+            tools::take_config_comments_backup,
+            tools::detect_configured_port,
+            tools::get_config_hash,
+            tools::get_publish_security,
+            tools::harden_publish_rules,
+            tools::get_server_options,
+            tools::set_server_options,
+            tools::set_uplink_options,
+            tools::get_middlewares,
+            tools::set_middleware_enabled,
+            tools::get_audit_config,
+            tools::set_audit_config,
+            tools::test_audit,
             tools::get_packages,
             tools::get_package_count,
+            tools::get_package_counts,
+            tools::get_registry_stats,
+            tools::verify_package_integrity,
+            tools::compute_integrity,
+            tools::rebuild_package_metadata,
+            tools::get_package_tarballs,
+            tools::get_package_publish_history,
+            tools::import_tarball,
+            tools::get_package_dependencies,
+            tools::find_packages_depending_on,
+            tools::find_prunable_versions,
+            tools::prune_old_versions,
+            tools::clone_package,
+            tools::rename_package,
+            tools::export_package_inventory,
+            tools::scan_storage_issues,
+            tools::clean_storage_issues,
+            tools::check_permissions,
+            tools::get_storage_disk_space,
             tools::delete_package,
+            tools::archive_package,
+            tools::restore_package_archive,
+            tools::clear_uplink_cache,
+            tools::uplink_impact,
+            tools::export_bundle,
+            tools::import_bundle,
+            tools::collect_diagnostics,
             tools::delete_packages,
+            tools::invalidate_private_cache,
+            tools::delete_packages_by_name,
+            tools::get_package_rules,
+            tools::get_configured_groups,
+            tools::match_package_rule,
+            tools::set_package_rule,
+            tools::delete_package_rule,
+            tools::get_web_ui_info,
+            tools::set_web_ui,
+            tools::get_url_prefix,
+            tools::set_url_prefix,
+            tools::test_uplink,
             tools::get_app_settings,
             tools::save_app_settings,
+            tools::export_settings,
+            tools::import_settings,
+            tools::get_allow_lan,
+            tools::set_allow_lan,
+            tools::set_proxy_settings,
+            tools::set_verbose_mode,
+            tools::set_tray_tooltip_template,
             tools::set_auto_start,
             tools::get_auto_start_status,
+            tools::ensure_autostart_minimized,
+            tools::check_for_update,
             tools::get_users,
             tools::add_user,
             tools::delete_user,
             tools::change_user_password,
+            tools::change_passwords,
+            tools::rehash_user,
+            tools::reset_users,
+            tools::test_login,
+            tools::reset_user_password,
+            tools::set_user_disabled,
             tools::get_user_count,
+            tools::get_user_groups,
+            tools::set_user_groups,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
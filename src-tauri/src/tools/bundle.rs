@@ -0,0 +1,224 @@
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+use super::config::{backup_config, write_config_file};
+use super::fsutil::atomic_write;
+use super::packages::get_storage_path;
+use super::settings::get_settings_path;
+use super::users::{get_htpasswd_path, get_user_meta_path, HtpasswdLock};
+use super::verdaccio::{get_config_path, VerdaccioProcess};
+
+/// 迁移包中的描述文件
+const MANIFEST_ENTRY: &str = "bundle.json";
+const CONFIG_ENTRY: &str = "config.yaml";
+const HTPASSWD_ENTRY: &str = "htpasswd";
+const SETTINGS_ENTRY: &str = "settings.json";
+const USER_META_ENTRY: &str = "user-meta.json";
+/// 存储目录在迁移包中的前缀
+const STORAGE_PREFIX: &str = "storage/";
+
+/// 迁移包描述
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub app_version: String,
+    pub created: String,
+    pub include_storage: bool,
+}
+
+/// 将单个文件写入 zip（文件不存在时跳过）
+fn add_file_entry<W: Write + std::io::Seek>(
+    zip: &mut zip::ZipWriter<W>,
+    entry_name: &str,
+    path: &Path,
+    options: zip::write::SimpleFileOptions,
+) -> Result<(), String> {
+    if !path.is_file() {
+        return Ok(());
+    }
+    let data = std::fs::read(path).map_err(|e| format!("读取 {} 失败: {}", path.display(), e))?;
+    zip.start_file(entry_name, options)
+        .map_err(|e| format!("写入迁移包失败: {}", e))?;
+    zip.write_all(&data).map_err(|e| format!("写入迁移包失败: {}", e))
+}
+
+/// 递归将目录写入 zip
+fn add_dir_entries<W: Write + std::io::Seek>(
+    zip: &mut zip::ZipWriter<W>,
+    prefix: &str,
+    dir: &Path,
+    options: zip::write::SimpleFileOptions,
+) -> Result<(), String> {
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("读取目录失败: {}", e))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let entry_name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+        if path.is_dir() {
+            add_dir_entries(zip, &format!("{}/", entry_name), &path, options)?;
+        } else {
+            add_file_entry(zip, &entry_name, &path, options)?;
+        }
+    }
+    Ok(())
+}
+
+/// 写入迁移包
+fn write_bundle(dest_path: &Path, include_storage: bool) -> Result<(), String> {
+    let file = std::fs::File::create(dest_path).map_err(|e| format!("创建迁移包失败: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    let manifest = BundleManifest {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created: chrono::Local::now().to_rfc3339(),
+        include_storage,
+    };
+    let manifest = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| format!("序列化迁移包描述失败: {}", e))?;
+    zip.start_file(MANIFEST_ENTRY, options)
+        .map_err(|e| format!("写入迁移包失败: {}", e))?;
+    zip.write_all(&manifest).map_err(|e| format!("写入迁移包失败: {}", e))?;
+
+    add_file_entry(&mut zip, CONFIG_ENTRY, &get_config_path()?, options)?;
+    add_file_entry(&mut zip, HTPASSWD_ENTRY, &get_htpasswd_path()?, options)?;
+    add_file_entry(&mut zip, SETTINGS_ENTRY, &get_settings_path()?, options)?;
+    add_file_entry(&mut zip, USER_META_ENTRY, &get_user_meta_path()?, options)?;
+
+    let storage_path = get_storage_path()?;
+    if include_storage && storage_path.exists() {
+        add_dir_entries(&mut zip, STORAGE_PREFIX, &storage_path, options)?;
+    }
+
+    zip.finish().map_err(|e| format!("写入迁移包失败: {}", e))?;
+    Ok(())
+}
+
+/// 读取迁移包中的单个文件
+fn read_entry(archive: &mut zip::ZipArchive<std::fs::File>, name: &str) -> Result<Option<Vec<u8>>, String> {
+    let mut entry = match archive.by_name(name) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(format!("读取迁移包失败: {}", e)),
+    };
+    let mut data = Vec::new();
+    entry
+        .read_to_end(&mut data)
+        .map_err(|e| format!("读取迁移包失败: {}", e))?;
+    Ok(Some(data))
+}
+
+/// 导出迁移包（Verdaccio 配置、htpasswd、应用设置、用户附加信息，可选包含包存储目录）
+///
+/// 包含存储目录时体积可能很大，在后台线程中执行
+#[tauri::command]
+pub async fn export_bundle(dest_path: String, include_storage: bool) -> Result<(), String> {
+    let dest_path = PathBuf::from(dest_path);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let result = write_bundle(&dest_path, include_storage);
+        if result.is_err() {
+            let _ = std::fs::remove_file(&dest_path);
+        }
+        result
+    })
+    .await
+    .map_err(|e| format!("导出迁移包失败: {}", e))?
+}
+
+/// 在后台线程中还原迁移包：配置、应用设置、用户附加信息及存储目录
+///
+/// htpasswd 需要在持有文件锁时写入，以原始数据返回，由调用方加锁后写入
+fn restore_bundle(zip_path: &Path) -> Result<(BundleManifest, Option<Vec<u8>>), String> {
+    let file = std::fs::File::open(zip_path).map_err(|e| format!("打开迁移包失败: {}", e))?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| format!("读取迁移包失败: {}", e))?;
+
+    let manifest: BundleManifest = read_entry(&mut archive, MANIFEST_ENTRY)?
+        .ok_or_else(|| "不是有效的迁移包（缺少 bundle.json）".to_string())
+        .and_then(|data| {
+            serde_json::from_slice(&data).map_err(|e| format!("解析迁移包描述失败: {}", e))
+        })?;
+
+    if let Some(data) = read_entry(&mut archive, CONFIG_ENTRY)? {
+        let config_path = get_config_path()?;
+        if config_path.exists() {
+            backup_config()?;
+        }
+        ensure_parent(&config_path)?;
+        write_config_file(&config_path, data).map_err(|e| format!("写入配置文件失败: {}", e))?;
+    }
+
+    let htpasswd = read_entry(&mut archive, HTPASSWD_ENTRY)?;
+
+    for (entry_name, path) in [
+        (SETTINGS_ENTRY, get_settings_path()?),
+        (USER_META_ENTRY, get_user_meta_path()?),
+    ] {
+        if let Some(data) = read_entry(&mut archive, entry_name)? {
+            ensure_parent(&path)?;
+            atomic_write(&path, data).map_err(|e| format!("写入 {} 失败: {}", entry_name, e))?;
+        }
+    }
+
+    let storage_path = get_storage_path()?;
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| format!("读取迁移包失败: {}", e))?;
+        if entry.is_dir() {
+            continue;
+        }
+        let Some(relative) = entry
+            .enclosed_name()
+            .and_then(|p| p.strip_prefix(STORAGE_PREFIX).ok().map(|p| p.to_path_buf()))
+        else {
+            continue;
+        };
+        let target = storage_path.join(&relative);
+        ensure_parent(&target)?;
+        let mut output = std::fs::File::create(&target)
+            .map_err(|e| format!("还原 {} 失败: {}", relative.display(), e))?;
+        std::io::copy(&mut entry, &mut output)
+            .map_err(|e| format!("还原 {} 失败: {}", relative.display(), e))?;
+    }
+
+    Ok((manifest, htpasswd))
+}
+
+/// 确保文件所在目录存在
+fn ensure_parent(path: &Path) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+    }
+    Ok(())
+}
+
+/// 从迁移包恢复配置与数据（服务运行时拒绝执行）
+///
+/// 覆盖前会备份当前 Verdaccio 配置；存储目录中的同名文件会被覆盖，其余文件保留。
+/// 解压与还原存储目录可能耗时较长，在后台线程中执行
+#[tauri::command]
+pub async fn import_bundle(
+    process: State<'_, VerdaccioProcess>,
+    lock: State<'_, HtpasswdLock>,
+    zip_path: String,
+) -> Result<BundleManifest, String> {
+    if process.check_running() {
+        return Err("请先停止 Verdaccio 再导入迁移包".to_string());
+    }
+
+    let zip_path = PathBuf::from(zip_path);
+    let (manifest, htpasswd) =
+        tauri::async_runtime::spawn_blocking(move || restore_bundle(&zip_path))
+            .await
+            .map_err(|e| format!("导入迁移包失败: {}", e))??;
+
+    // 配置已恢复，htpasswd 路径可能随之变化，需在写入配置后再解析
+    if let Some(data) = htpasswd {
+        let htpasswd_path = get_htpasswd_path()?;
+        ensure_parent(&htpasswd_path)?;
+        let _guard = lock.acquire(&htpasswd_path).await?;
+        atomic_write(&htpasswd_path, data).map_err(|e| format!("写入 htpasswd 失败: {}", e))?;
+    }
+
+    Ok(manifest)
+}
@@ -0,0 +1,1266 @@
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::path::Path;
+use std::sync::Mutex;
+
+use super::fsutil::atomic_write;
+use super::settings::http_client_builder;
+use super::verdaccio::get_config_path;
+
+/// 允许匿名发布的权限值
+const ANONYMOUS_PUBLISH_VALUES: [&str; 2] = ["$all", "$anonymous"];
+
+/// Verdaccio 内置的权限值
+const BUILTIN_PERMISSIONS: [&str; 3] = ["$all", "$authenticated", "$anonymous"];
+
+/// 读取并解析 Verdaccio 配置文件
+pub(crate) fn load_config() -> Result<Value, String> {
+    let config_path = get_config_path()?;
+
+    if !config_path.exists() {
+        return Err("配置文件不存在".to_string());
+    }
+
+    let content = std::fs::read_to_string(&config_path)
+        .map_err(|e| format!("读取配置文件失败: {}", e))?;
+
+    serde_yaml::from_str(&content).map_err(|e| format!("解析配置文件失败: {}", e))
+}
+
+/// 备份当前配置文件到同目录下带时间戳的 .bak 文件，返回备份路径
+pub(crate) fn backup_config() -> Result<std::path::PathBuf, String> {
+    let config_path = get_config_path()?;
+    let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let backup_path = config_path.with_extension(format!("yaml.{}.bak", timestamp));
+
+    std::fs::copy(&config_path, &backup_path).map_err(|e| format!("备份配置文件失败: {}", e))?;
+
+    Ok(backup_path)
+}
+
+/// 将权限字段（字符串或字符串数组）拆分为单独的值
+pub(crate) fn permission_values(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::String(s)) => s.split_whitespace().map(|v| v.to_string()).collect(),
+        Some(Value::Sequence(seq)) => seq
+            .iter()
+            .filter_map(|v| v.as_str())
+            .map(|v| v.to_string())
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// 获取允许匿名发布的包规则模式
+pub(crate) fn anonymous_publish_patterns(config: &Value) -> Vec<String> {
+    let Some(packages) = config.get("packages").and_then(|p| p.as_mapping()) else {
+        return vec![];
+    };
+
+    packages
+        .iter()
+        .filter(|(_, rule)| {
+            permission_values(rule.get("publish"))
+                .iter()
+                .any(|v| ANONYMOUS_PUBLISH_VALUES.contains(&v.as_str()))
+        })
+        .filter_map(|(pattern, _)| pattern.as_str().map(|s| s.to_string()))
+        .collect()
+}
+
+/// 获取包规则 access / publish 中引用的组名（排除内置权限值，去重并排序）
+pub(crate) fn configured_groups(config: &Value) -> Vec<String> {
+    let Some(packages) = get_packages_mapping(config) else {
+        return vec![];
+    };
+
+    let groups: std::collections::BTreeSet<String> = packages
+        .values()
+        .flat_map(|rule| {
+            let mut values = permission_values(rule.get("access"));
+            values.extend(permission_values(rule.get("publish")));
+            values
+        })
+        .filter(|v| !BUILTIN_PERMISSIONS.contains(&v.as_str()))
+        .collect();
+
+    groups.into_iter().collect()
+}
+
+/// 从单个 listen 值中解析端口（如 `4873`、`0.0.0.0:4873`、`[::1]:4873`、`http://localhost:4873/`）
+fn parse_listen_port(listen: &str) -> Option<u16> {
+    let listen = listen.trim();
+    if listen.starts_with("unix:") {
+        return None;
+    }
+    let without_scheme = listen.split_once("://").map_or(listen, |(_, rest)| rest);
+    let host_port = without_scheme.split('/').next().unwrap_or_default();
+    host_port
+        .rsplit(':')
+        .next()
+        .and_then(|port| port.parse().ok())
+}
+
+/// 读取配置中 `listen` 指定的端口（字符串或数组时取第一个可解析的值）
+pub(crate) fn configured_port(config: &Value) -> Option<u16> {
+    match config.get("listen")? {
+        Value::Number(n) => n.as_u64().and_then(|p| u16::try_from(p).ok()),
+        Value::String(s) => parse_listen_port(s),
+        Value::Sequence(items) => items
+            .iter()
+            .filter_map(|item| item.as_str())
+            .find_map(parse_listen_port),
+        _ => None,
+    }
+}
+
+/// 计算配置内容的哈希（sha256 十六进制），用于前端判断配置是否变化
+pub(crate) fn config_hash(content: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    format!("{:x}", Sha256::digest(content))
+}
+
+/// 应用自身最近一次写入配置文件的内容哈希，配置监听据此忽略自身保存引起的变更
+static LAST_WRITTEN_CONFIG_HASH: Mutex<Option<String>> = Mutex::new(None);
+
+/// 原子写入配置文件并记录内容哈希（应用内对 config.yaml 的写入都应通过此函数）
+pub(crate) fn write_config_file(path: &Path, content: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let content = content.as_ref();
+    // 先记录哈希再写入，监听回调触发时已能识别为自身写入
+    if let Ok(mut last) = LAST_WRITTEN_CONFIG_HASH.lock() {
+        *last = Some(config_hash(content));
+    }
+    atomic_write(path, content)
+}
+
+/// 判断内容哈希是否与应用自身最近一次写入的配置一致
+pub(crate) fn is_own_config_write(hash: &str) -> bool {
+    LAST_WRITTEN_CONFIG_HASH
+        .lock()
+        .is_ok_and(|last| last.as_deref() == Some(hash))
+}
+
+/// 包访问规则（对应配置中 `packages` 下的一项）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageRule {
+    pub pattern: String,
+    pub access: Option<String>,
+    pub publish: Option<String>,
+    pub proxy: Option<String>,
+}
+
+/// Web UI 信息
+#[derive(Debug, Clone, Serialize)]
+pub struct WebUiInfo {
+    pub enabled: bool,
+    pub url: String,
+    pub title: Option<String>,
+}
+
+/// 上游仓库连通性测试结果
+#[derive(Debug, Clone, Serialize)]
+pub struct UplinkTestResult {
+    pub reachable: bool,
+    pub status: Option<u16>,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// 上游仓库调优选项（对应配置中 `uplinks.<name>` 下的同名字段）
+///
+/// `timeout`、`maxage`、`fail_timeout` 为 Verdaccio 时长（如 `30s`、`2m`、`1h 30m`），
+/// `max_fails` 为连续失败次数上限
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UplinkOptions {
+    pub timeout: Option<String>,
+    pub maxage: Option<String>,
+    pub max_fails: Option<u64>,
+    pub fail_timeout: Option<String>,
+}
+
+/// 中间件信息（对应配置中 `middlewares` 下的一项）
+#[derive(Debug, Clone, Serialize)]
+pub struct MiddlewareInfo {
+    pub name: String,
+    pub enabled: bool,
+}
+
+/// audit 中间件配置（转发 `npm audit` 请求到上游）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditConfig {
+    pub enabled: bool,
+    /// 访问上游 audit 接口时是否校验证书，未配置时 Verdaccio 默认为 true
+    pub strict_ssl: Option<bool>,
+}
+
+/// audit 接口测试结果
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditTestResult {
+    pub working: bool,
+    pub status: Option<u16>,
+    pub error: Option<String>,
+}
+
+/// 发布权限安全检查结果
+#[derive(Debug, Clone, Serialize)]
+pub struct PublishSecurity {
+    /// 是否存在允许匿名发布的规则
+    pub anonymous_publish: bool,
+    pub patterns: Vec<String>,
+}
+
+/// 收紧发布权限的结果
+#[derive(Debug, Clone, Serialize)]
+pub struct HardenPublishResult {
+    /// 被修改的规则模式
+    pub changed: Vec<String>,
+    /// 修改前的配置备份路径（没有规则需要修改时为 None）
+    pub backup_path: Option<String>,
+}
+
+/// 服务端调优选项
+///
+/// `keep_alive_timeout` 对应 `server.keepAliveTimeout`（秒），
+/// `max_body_size` 对应顶层的 `max_body_size`（Verdaccio 从顶层读取该项）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerOptions {
+    pub keep_alive_timeout: Option<u64>,
+    pub max_body_size: Option<String>,
+}
+
+/// 因重新序列化丢失注释而备份的配置文件路径，尚未通知前端
+static CONFIG_COMMENTS_BACKUP: Mutex<Option<String>> = Mutex::new(None);
+
+/// 判断配置内容中是否包含注释行
+fn has_yaml_comments(content: &str) -> bool {
+    content.lines().any(|line| line.trim_start().starts_with('#'))
+}
+
+/// 序列化并保存 Verdaccio 配置
+///
+/// 重新序列化会丢失原文件中的注释与手写格式，原文件含有注释时先备份，
+/// 备份路径通过 [`take_config_comments_backup`] 通知前端
+pub(crate) fn save_config(config: &Value) -> Result<(), String> {
+    let content = serde_yaml::to_string(config)
+        .map_err(|e| format!("序列化配置失败: {}", e))?;
+
+    let config_path = get_config_path()?;
+    let original = std::fs::read_to_string(&config_path).unwrap_or_default();
+    if has_yaml_comments(&original) {
+        let backup_path = backup_config()?;
+        if let Ok(mut pending) = CONFIG_COMMENTS_BACKUP.lock() {
+            *pending = Some(backup_path.to_string_lossy().to_string());
+        }
+    }
+
+    write_config_file(&config_path, content).map_err(|e| format!("保存配置文件失败: {}", e))
+}
+
+/// 获取并清除因保存配置丢失注释而生成的备份路径（没有时返回 None）
+#[tauri::command]
+pub async fn take_config_comments_backup() -> Result<Option<String>, String> {
+    Ok(CONFIG_COMMENTS_BACKUP
+        .lock()
+        .map_err(|e| e.to_string())?
+        .take())
+}
+
+/// 将规则字段（字符串或字符串数组）合并为以空格分隔的字符串
+fn join_rule_field(value: Option<&Value>) -> Option<String> {
+    let values = permission_values(value);
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.join(" "))
+    }
+}
+
+/// 判断是否为合法的组名
+pub(crate) fn is_valid_group_name(name: &str) -> bool {
+    let group_re = regex::Regex::new(r"^[A-Za-z0-9_][A-Za-z0-9_.@-]*$").unwrap();
+    group_re.is_match(name)
+}
+
+/// 校验 access / publish 权限值（`$all` / `$authenticated` / `$anonymous` 或组名）
+fn validate_permission(field: &str, value: &str) -> Result<(), String> {
+    if value.split_whitespace().next().is_none() {
+        return Err(format!("{} 不能为空", field));
+    }
+
+    for token in value.split_whitespace() {
+        if BUILTIN_PERMISSIONS.contains(&token) {
+            continue;
+        }
+        if !is_valid_group_name(token) {
+            return Err(format!("{} 包含无效的权限值: {}", field, token));
+        }
+    }
+
+    Ok(())
+}
+
+/// 校验 Web UI 主题色（#rgb 或 #rrggbb）
+fn validate_color(color: &str) -> Result<(), String> {
+    let color_re = regex::Regex::new(r"^#([0-9a-fA-F]{3}|[0-9a-fA-F]{6})$").unwrap();
+    if color_re.is_match(color) {
+        Ok(())
+    } else {
+        Err(format!("无效的颜色值: {}，请使用 #RRGGBB 格式", color))
+    }
+}
+
+/// 校验 Web UI logo（http(s) 地址或存在的本地图片文件）
+fn validate_logo(logo: &str) -> Result<(), String> {
+    if logo.starts_with("http://") || logo.starts_with("https://") {
+        return Ok(());
+    }
+
+    let path = std::path::Path::new(logo);
+    let is_image = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .is_some_and(|e| ["png", "jpg", "jpeg", "gif", "svg", "ico", "webp"].contains(&e.as_str()));
+
+    if !is_image {
+        return Err("logo 必须是 http(s) 地址或图片文件".to_string());
+    }
+    if !path.exists() {
+        return Err(format!("logo 文件不存在: {}", logo));
+    }
+
+    Ok(())
+}
+
+/// 校验请求体大小（如 10mb、512kb、1gb）
+fn validate_body_size(size: &str) -> Result<(), String> {
+    let size_re = regex::Regex::new(r"(?i)^[0-9]+(\.[0-9]+)?\s*(b|kb|mb|gb)?$").unwrap();
+    if size_re.is_match(size) {
+        Ok(())
+    } else {
+        Err(format!("无效的大小: {}，请使用如 10mb 的格式", size))
+    }
+}
+
+/// 校验 `url_prefix`（以 `/` 开头的路径或完整的 http(s) 地址）
+fn validate_url_prefix(prefix: &str) -> Result<(), String> {
+    if prefix.starts_with('/') {
+        return Ok(());
+    }
+
+    match reqwest::Url::parse(prefix) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") && url.has_host() => Ok(()),
+        _ => Err(format!(
+            "无效的 url_prefix: {}，请使用以 / 开头的路径或完整的 http(s) 地址",
+            prefix
+        )),
+    }
+}
+
+/// 校验 Verdaccio 时长（数字加可选单位 ms/s/m/h/d/w/M/y，可组合如 `1h 30m`）
+fn validate_duration(field: &str, duration: &str) -> Result<(), String> {
+    let duration_re =
+        regex::Regex::new(r"^([0-9]+(\.[0-9]+)?\s*(ms|s|m|h|d|w|M|y)?\s*)+$").unwrap();
+    if duration_re.is_match(duration) {
+        Ok(())
+    } else {
+        Err(format!("无效的 {}: {}，请使用如 30s、2m 的格式", field, duration))
+    }
+}
+
+/// 设置或移除映射中的字符串字段（值为空时移除）
+fn set_optional_string(mapping: &mut Mapping, key: &str, value: Option<String>) {
+    match value {
+        Some(v) => {
+            mapping.insert(key.into(), Value::String(v));
+        }
+        None => {
+            mapping.shift_remove(key);
+        }
+    }
+}
+
+/// 获取配置中的 `packages` 映射
+fn get_packages_mapping(config: &Value) -> Option<&Mapping> {
+    config.get("packages").and_then(|p| p.as_mapping())
+}
+
+/// 获取包访问规则列表（保持配置中的顺序，Verdaccio 按从上到下的顺序匹配）
+#[tauri::command]
+pub async fn get_package_rules() -> Result<Vec<PackageRule>, String> {
+    let config = load_config()?;
+
+    let Some(packages) = get_packages_mapping(&config) else {
+        return Ok(vec![]);
+    };
+
+    Ok(packages
+        .iter()
+        .filter_map(|(pattern, rule)| {
+            Some(PackageRule {
+                pattern: pattern.as_str()?.to_string(),
+                access: join_rule_field(rule.get("access")),
+                publish: join_rule_field(rule.get("publish")),
+                proxy: join_rule_field(rule.get("proxy")),
+            })
+        })
+        .collect())
+}
+
+/// 将包规则模式转换为正则，与 Verdaccio 使用的 minimatch 语义一致：
+/// `**` 匹配任意字符，`*` 与 `?` 不跨越 `/`，支持 `{a,b}` 多选与 `[...]` 字符集；
+/// 不支持以 `!` 开头的取反模式，遇到时返回错误
+fn package_pattern_regex(pattern: &str) -> Result<regex::Regex, String> {
+    if pattern.starts_with('!') {
+        return Err(format!("不支持取反的包规则模式: {}", pattern));
+    }
+
+    let mut re = String::from("^");
+    let mut brace_depth = 0;
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                re.push_str(".*");
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            '{' => {
+                brace_depth += 1;
+                re.push_str("(?:");
+            }
+            ',' if brace_depth > 0 => re.push('|'),
+            '}' if brace_depth > 0 => {
+                brace_depth -= 1;
+                re.push(')');
+            }
+            '[' => {
+                let mut class = String::from("[");
+                if chars.next_if(|&c| c == '!' || c == '^').is_some() {
+                    class.push('^');
+                }
+                let mut closed = false;
+                for c in chars.by_ref() {
+                    match c {
+                        ']' => {
+                            closed = true;
+                            break;
+                        }
+                        '\\' | '[' | '&' | '~' => {
+                            class.push('\\');
+                            class.push(c);
+                        }
+                        c => class.push(c),
+                    }
+                }
+                if !closed {
+                    return Err(format!("包规则模式中的 [ 未闭合: {}", pattern));
+                }
+                class.push(']');
+                re.push_str(&class);
+            }
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    if brace_depth > 0 {
+        return Err(format!("包规则模式中的 {{ 未闭合: {}", pattern));
+    }
+    re.push('$');
+    regex::Regex::new(&re).map_err(|e| format!("无效的包规则模式 {}: {}", pattern, e))
+}
+
+/// 按顺序取第一条匹配包名的规则，无法解析的规则模式跳过而不是中断整个查找
+fn first_matching_rule(rules: Vec<PackageRule>, package_name: &str) -> Option<PackageRule> {
+    rules.into_iter().find(|rule| {
+        package_pattern_regex(&rule.pattern).is_ok_and(|re| re.is_match(package_name))
+    })
+}
+
+/// 预览包名会命中哪条包规则（按配置顺序取第一条匹配的规则），没有规则匹配时返回 None
+#[tauri::command]
+pub async fn match_package_rule(package_name: String) -> Result<Option<PackageRule>, String> {
+    let package_name = package_name.trim();
+    if package_name.is_empty() {
+        return Err("包名不能为空".to_string());
+    }
+
+    Ok(first_matching_rule(get_package_rules().await?, package_name))
+}
+
+/// 获取包规则 access / publish 中引用的组名（去重并排序，不含 `$all` 等内置权限值）
+#[tauri::command]
+pub async fn get_configured_groups() -> Result<Vec<String>, String> {
+    if !get_config_path()?.exists() {
+        return Ok(vec![]);
+    }
+    Ok(configured_groups(&load_config()?))
+}
+
+/// 将新规则插入到第一条同样能匹配该模式的已有规则之前（Verdaccio 按顺序取第一条匹配的规则），
+/// 没有这样的规则时追加到末尾
+fn insert_package_rule(packages: &mut Mapping, key: Value, rule: Mapping) {
+    // 以通配符替换为普通字符后的包名作为新规则覆盖范围的样例
+    let sample: String = key
+        .as_str()
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c == '*' || c == '?' { 'x' } else { c })
+        .collect();
+    let shadows = |pattern: &Value| {
+        pattern
+            .as_str()
+            .and_then(|p| package_pattern_regex(p).ok())
+            .is_some_and(|re| re.is_match(&sample))
+    };
+
+    let mut entries: Vec<(Value, Value)> = std::mem::take(packages).into_iter().collect();
+    let position = entries
+        .iter()
+        .position(|(pattern, _)| shadows(pattern))
+        .unwrap_or(entries.len());
+    entries.insert(position, (key, Value::Mapping(rule)));
+    *packages = entries.into_iter().collect();
+}
+
+/// 新增或更新包访问规则
+///
+/// 已存在的规则原地更新（保留其他字段）；新规则插入到第一条同样能匹配它的已有规则之前，避免被其遮蔽
+#[tauri::command]
+pub async fn set_package_rule(
+    pattern: String,
+    access: String,
+    publish: String,
+    proxy: Option<String>,
+) -> Result<(), String> {
+    let pattern = pattern.trim().to_string();
+    if pattern.is_empty() {
+        return Err("规则模式不能为空".to_string());
+    }
+    validate_permission("access", &access)?;
+    validate_permission("publish", &publish)?;
+
+    let mut config = load_config()?;
+    let root = config
+        .as_mapping_mut()
+        .ok_or_else(|| "配置文件格式无效".to_string())?;
+
+    let key = Value::String(pattern.clone());
+    let packages_key = Value::String("packages".to_string());
+    let packages = root
+        .entry(packages_key)
+        .or_insert_with(|| Value::Mapping(Mapping::new()));
+    let packages = packages
+        .as_mapping_mut()
+        .ok_or_else(|| "packages 配置格式无效".to_string())?;
+
+    let mut rule = packages
+        .get(&key)
+        .and_then(|r| r.as_mapping())
+        .cloned()
+        .unwrap_or_default();
+    rule.insert("access".into(), Value::String(access.trim().to_string()));
+    rule.insert("publish".into(), Value::String(publish.trim().to_string()));
+    match proxy.as_deref().map(str::trim) {
+        Some(p) if !p.is_empty() => {
+            rule.insert("proxy".into(), Value::String(p.to_string()));
+        }
+        _ => {
+            rule.shift_remove("proxy");
+        }
+    }
+
+    if packages.contains_key(&key) {
+        packages.insert(key, Value::Mapping(rule));
+    } else {
+        insert_package_rule(packages, key, rule);
+    }
+
+    save_config(&config)
+}
+
+/// 删除包访问规则
+#[tauri::command]
+pub async fn delete_package_rule(pattern: String) -> Result<(), String> {
+    let mut config = load_config()?;
+
+    let packages = config
+        .get_mut("packages")
+        .and_then(|p| p.as_mapping_mut())
+        .ok_or_else(|| "配置中没有 packages 规则".to_string())?;
+
+    // shift_remove 保持其余规则的顺序
+    if packages.shift_remove(pattern.as_str()).is_none() {
+        return Err(format!("规则 {} 不存在", pattern));
+    }
+
+    save_config(&config)
+}
+
+/// 获取 Verdaccio Web UI 信息（未配置 `web.enable` 时默认启用）
+#[tauri::command]
+pub async fn get_web_ui_info(port: u16) -> Result<WebUiInfo, String> {
+    let config = load_config()?;
+    let web = config.get("web");
+
+    let enabled = web
+        .and_then(|w| w.get("enable"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true);
+    let title = web
+        .and_then(|w| w.get("title"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+
+    Ok(WebUiInfo {
+        enabled,
+        url: format!("http://localhost:{}/", port),
+        title,
+    })
+}
+
+/// 设置 Verdaccio Web UI 的标题、logo 与主题色（传入空值时移除对应配置，恢复默认）
+#[tauri::command]
+pub async fn set_web_ui(
+    title: Option<String>,
+    logo: Option<String>,
+    primary_color: Option<String>,
+) -> Result<(), String> {
+    let non_empty = |v: Option<String>| v.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    let title = non_empty(title);
+    let logo = non_empty(logo);
+    let primary_color = non_empty(primary_color);
+
+    if let Some(logo) = &logo {
+        validate_logo(logo)?;
+    }
+    if let Some(color) = &primary_color {
+        validate_color(color)?;
+    }
+
+    let mut config = load_config()?;
+    let root = config
+        .as_mapping_mut()
+        .ok_or_else(|| "配置文件格式无效".to_string())?;
+    let web = root
+        .entry("web".into())
+        .or_insert_with(|| Value::Mapping(Mapping::new()))
+        .as_mapping_mut()
+        .ok_or_else(|| "web 配置格式无效".to_string())?;
+
+    set_optional_string(web, "title", title);
+    set_optional_string(web, "logo", logo);
+    set_optional_string(web, "primary_color", primary_color);
+
+    save_config(&config)
+}
+
+/// 获取 Verdaccio 对外发布的基础地址（`url_prefix`），未配置时返回 None
+#[tauri::command]
+pub async fn get_url_prefix() -> Result<Option<String>, String> {
+    let config = load_config()?;
+    Ok(config
+        .get("url_prefix")
+        .and_then(|v| v.as_str())
+        .map(String::from))
+}
+
+/// 设置 Verdaccio 对外发布的基础地址（`url_prefix`），反向代理部署时用于生成正确的 tarball 地址
+///
+/// 传入空值时移除该配置
+#[tauri::command]
+pub async fn set_url_prefix(prefix: Option<String>) -> Result<(), String> {
+    let prefix = prefix.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    if let Some(prefix) = &prefix {
+        validate_url_prefix(prefix)?;
+    }
+
+    let mut config = load_config()?;
+    let root = config
+        .as_mapping_mut()
+        .ok_or_else(|| "配置文件格式无效".to_string())?;
+    set_optional_string(root, "url_prefix", prefix);
+
+    save_config(&config)
+}
+
+/// 获取配置文件内容的哈希，前端可据此跳过未变化配置的重新解析（配置文件不存在时返回 None）
+#[tauri::command]
+pub async fn get_config_hash() -> Result<Option<String>, String> {
+    let config_path = get_config_path()?;
+    if !config_path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read(&config_path).map_err(|e| format!("读取配置文件失败: {}", e))?;
+    Ok(Some(config_hash(&content)))
+}
+
+/// 检查包规则中是否有允许匿名发布（`$all` / `$anonymous`）的规则
+#[tauri::command]
+pub async fn get_publish_security() -> Result<PublishSecurity, String> {
+    let patterns = anonymous_publish_patterns(&load_config()?);
+    Ok(PublishSecurity {
+        anonymous_publish: !patterns.is_empty(),
+        patterns,
+    })
+}
+
+/// 将允许匿名发布的规则的 publish 中的 `$all` / `$anonymous` 改为 `$authenticated`
+///
+/// 修改前备份配置文件，返回被修改的规则
+#[tauri::command]
+pub async fn harden_publish_rules() -> Result<HardenPublishResult, String> {
+    let mut config = load_config()?;
+    let changed = anonymous_publish_patterns(&config);
+
+    if changed.is_empty() {
+        return Ok(HardenPublishResult {
+            changed,
+            backup_path: None,
+        });
+    }
+
+    let backup_path = backup_config()?;
+
+    if let Some(packages) = config.get_mut("packages").and_then(|p| p.as_mapping_mut()) {
+        for pattern in &changed {
+            let Some(rule) = packages
+                .get_mut(pattern.as_str())
+                .and_then(|r| r.as_mapping_mut())
+            else {
+                continue;
+            };
+
+            let mut values: Vec<String> = Vec::new();
+            for value in permission_values(rule.get("publish")) {
+                let value = if ANONYMOUS_PUBLISH_VALUES.contains(&value.as_str()) {
+                    "$authenticated".to_string()
+                } else {
+                    value
+                };
+                if !values.contains(&value) {
+                    values.push(value);
+                }
+            }
+            rule.insert("publish".into(), Value::String(values.join(" ")));
+        }
+    }
+
+    save_config(&config)?;
+
+    Ok(HardenPublishResult {
+        changed,
+        backup_path: Some(backup_path.to_string_lossy().to_string()),
+    })
+}
+
+/// 获取服务端调优选项
+#[tauri::command]
+pub async fn get_server_options() -> Result<ServerOptions, String> {
+    let config = load_config()?;
+
+    let keep_alive_timeout = config
+        .get("server")
+        .and_then(|s| s.get("keepAliveTimeout"))
+        .and_then(|v| v.as_u64());
+    let max_body_size = config.get("max_body_size").and_then(|v| match v {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    });
+
+    Ok(ServerOptions {
+        keep_alive_timeout,
+        max_body_size,
+    })
+}
+
+/// 设置服务端调优选项（传入空值时移除对应配置，恢复 Verdaccio 默认值）
+#[tauri::command]
+pub async fn set_server_options(options: ServerOptions) -> Result<(), String> {
+    if options.keep_alive_timeout == Some(0) {
+        return Err("keepAliveTimeout 必须为正整数".to_string());
+    }
+    let max_body_size = options
+        .max_body_size
+        .map(|s| s.trim().to_lowercase())
+        .filter(|s| !s.is_empty());
+    if let Some(size) = &max_body_size {
+        validate_body_size(size)?;
+    }
+
+    let mut config = load_config()?;
+    let root = config
+        .as_mapping_mut()
+        .ok_or_else(|| "配置文件格式无效".to_string())?;
+
+    set_optional_string(root, "max_body_size", max_body_size);
+
+    let server = root
+        .entry("server".into())
+        .or_insert_with(|| Value::Mapping(Mapping::new()))
+        .as_mapping_mut()
+        .ok_or_else(|| "server 配置格式无效".to_string())?;
+    match options.keep_alive_timeout {
+        Some(timeout) => {
+            server.insert("keepAliveTimeout".into(), Value::Number(timeout.into()));
+        }
+        None => {
+            server.shift_remove("keepAliveTimeout");
+        }
+    }
+
+    save_config(&config)
+}
+
+/// 设置上游仓库的超时、缓存时长与失败重试选项（传入空值时移除对应配置，恢复 Verdaccio 默认值）
+#[tauri::command]
+pub async fn set_uplink_options(name: String, options: UplinkOptions) -> Result<(), String> {
+    let non_empty = |v: Option<String>| v.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    let timeout = non_empty(options.timeout);
+    let maxage = non_empty(options.maxage);
+    let fail_timeout = non_empty(options.fail_timeout);
+
+    for (field, value) in [("timeout", &timeout), ("maxage", &maxage), ("fail_timeout", &fail_timeout)] {
+        if let Some(value) = value {
+            validate_duration(field, value)?;
+        }
+    }
+
+    let mut config = load_config()?;
+    let uplink = config
+        .get_mut("uplinks")
+        .and_then(|u| u.get_mut(name.as_str()))
+        .ok_or_else(|| format!("上游仓库 {} 不存在", name))?
+        .as_mapping_mut()
+        .ok_or_else(|| format!("上游仓库 {} 配置格式无效", name))?;
+
+    set_optional_string(uplink, "timeout", timeout);
+    set_optional_string(uplink, "maxage", maxage);
+    set_optional_string(uplink, "fail_timeout", fail_timeout);
+    match options.max_fails {
+        Some(max_fails) => {
+            uplink.insert("max_fails".into(), Value::Number(max_fails.into()));
+        }
+        None => {
+            uplink.shift_remove("max_fails");
+        }
+    }
+
+    save_config(&config)
+}
+
+/// 从现有配置中检测 Verdaccio 监听端口，配置缺失或未指定 `listen` 时返回 None
+#[tauri::command]
+pub async fn detect_configured_port() -> Result<Option<u16>, String> {
+    if !get_config_path()?.exists() {
+        return Ok(None);
+    }
+    Ok(configured_port(&load_config()?))
+}
+
+/// 获取配置中的中间件列表（未配置 `enabled` 时视为启用）
+#[tauri::command]
+pub async fn get_middlewares() -> Result<Vec<MiddlewareInfo>, String> {
+    let config = load_config()?;
+    let Some(middlewares) = config.get("middlewares").and_then(|m| m.as_mapping()) else {
+        return Ok(vec![]);
+    };
+
+    Ok(middlewares
+        .iter()
+        .filter_map(|(name, value)| {
+            let name = name.as_str()?.to_string();
+            let enabled = value
+                .get("enabled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            Some(MiddlewareInfo { name, enabled })
+        })
+        .collect())
+}
+
+/// 启用或禁用配置中已有的中间件
+#[tauri::command]
+pub async fn set_middleware_enabled(name: String, enabled: bool) -> Result<(), String> {
+    let mut config = load_config()?;
+
+    let middleware = config
+        .get_mut("middlewares")
+        .and_then(|m| m.as_mapping_mut())
+        .and_then(|m| m.get_mut(name.as_str()))
+        .ok_or_else(|| format!("中间件 {} 不存在", name))?;
+
+    // `audit: ~` 这类空配置需要先转为映射
+    if middleware.is_null() {
+        *middleware = Value::Mapping(Mapping::new());
+    }
+    let middleware = middleware
+        .as_mapping_mut()
+        .ok_or_else(|| format!("中间件 {} 配置格式无效", name))?;
+    middleware.insert("enabled".into(), Value::Bool(enabled));
+
+    save_config(&config)
+}
+
+/// 获取 audit 中间件配置（未配置时视为禁用）
+#[tauri::command]
+pub async fn get_audit_config() -> Result<AuditConfig, String> {
+    let config = load_config()?;
+    let audit = config.get("middlewares").and_then(|m| m.get("audit"));
+
+    Ok(AuditConfig {
+        enabled: audit.is_some_and(|a| a.get("enabled").and_then(|v| v.as_bool()).unwrap_or(true)),
+        strict_ssl: audit.and_then(|a| a.get("strict_ssl")).and_then(|v| v.as_bool()),
+    })
+}
+
+/// 设置 audit 中间件配置（配置中没有 audit 时自动添加）
+#[tauri::command]
+pub async fn set_audit_config(audit: AuditConfig) -> Result<(), String> {
+    let mut config = load_config()?;
+    let root = config
+        .as_mapping_mut()
+        .ok_or_else(|| "配置文件格式无效".to_string())?;
+    let middlewares = root
+        .entry("middlewares".into())
+        .or_insert_with(|| Value::Mapping(Mapping::new()));
+    if middlewares.is_null() {
+        *middlewares = Value::Mapping(Mapping::new());
+    }
+    let entry = middlewares
+        .as_mapping_mut()
+        .ok_or_else(|| "middlewares 配置格式无效".to_string())?
+        .entry("audit".into())
+        .or_insert_with(|| Value::Mapping(Mapping::new()));
+    if entry.is_null() {
+        *entry = Value::Mapping(Mapping::new());
+    }
+    let entry = entry
+        .as_mapping_mut()
+        .ok_or_else(|| "audit 配置格式无效".to_string())?;
+
+    entry.insert("enabled".into(), Value::Bool(audit.enabled));
+    match audit.strict_ssl {
+        Some(strict_ssl) => {
+            entry.insert("strict_ssl".into(), Value::Bool(strict_ssl));
+        }
+        None => {
+            entry.shift_remove("strict_ssl");
+        }
+    }
+
+    save_config(&config)
+}
+
+/// 向本地 Verdaccio 发送一个示例 audit 请求，确认 audit 中间件已生效
+///
+/// 中间件未启用时 Verdaccio 会返回 404；能返回成功响应说明请求已被转发到上游
+#[tauri::command]
+pub async fn test_audit(port: u16) -> Result<AuditTestResult, String> {
+    let client = http_client_builder()?
+        .timeout(std::time::Duration::from_secs(15))
+        .build()
+        .map_err(|e| format!("创建 HTTP 客户端失败: {}", e))?;
+
+    let url = format!("http://localhost:{}/-/npm/v1/security/audits/quick", port);
+    let body = serde_json::json!({
+        "name": "mint-verdaccio-audit-test",
+        "version": "1.0.0",
+        "requires": {},
+        "dependencies": {},
+    });
+
+    Ok(match client.post(&url).json(&body).send().await {
+        Ok(response) => {
+            let status = response.status();
+            AuditTestResult {
+                working: status.is_success(),
+                status: Some(status.as_u16()),
+                error: (!status.is_success()).then(|| match status.as_u16() {
+                    404 => "audit 中间件未启用".to_string(),
+                    _ => format!("audit 请求失败: {}", status),
+                }),
+            }
+        }
+        Err(e) => AuditTestResult {
+            working: false,
+            status: None,
+            error: Some(e.to_string()),
+        },
+    })
+}
+
+/// 测试上游仓库是否可访问（请求 `{url}/-/ping`）
+#[tauri::command]
+pub async fn test_uplink(url: String) -> Result<UplinkTestResult, String> {
+    let url = url.trim();
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err("上游地址必须以 http:// 或 https:// 开头".to_string());
+    }
+
+    let client = http_client_builder()?
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| format!("创建 HTTP 客户端失败: {}", e))?;
+
+    let ping_url = format!("{}/-/ping", url.trim_end_matches('/'));
+    let started = std::time::Instant::now();
+
+    // 能收到任何 HTTP 响应即视为可达，状态码交由前端展示
+    Ok(match client.get(&ping_url).send().await {
+        Ok(response) => UplinkTestResult {
+            reachable: true,
+            status: Some(response.status().as_u16()),
+            latency_ms: Some(started.elapsed().as_millis() as u64),
+            error: None,
+        },
+        Err(e) => UplinkTestResult {
+            reachable: false,
+            status: None,
+            latency_ms: None,
+            error: Some(e.to_string()),
+        },
+    })
+}
+
+/// 对 URL 路径段进行百分号编码（仅保留非保留字符），scoped 包名中的 / 也会被编码
+fn encode_path_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// 在浏览器中打开包在 Verdaccio Web UI 中的详情页（仅在服务运行且 Web UI 启用时可用）
+#[tauri::command]
+pub async fn open_package_page(
+    app: tauri::AppHandle,
+    process: tauri::State<'_, super::verdaccio::VerdaccioProcess>,
+    port: u16,
+    package_name: String,
+) -> Result<(), String> {
+    use tauri_plugin_opener::OpenerExt;
+
+    if !process.check_running() {
+        return Err("Verdaccio 未运行".to_string());
+    }
+
+    let web_ui = get_web_ui_info(port).await?;
+    if !web_ui.enabled {
+        return Err("Verdaccio Web UI 未启用".to_string());
+    }
+
+    let url = format!("{}-/web/detail/{}", web_ui.url, encode_path_segment(&package_name));
+    app.opener()
+        .open_url(url, None::<&str>)
+        .map_err(|e| format!("打开包页面失败: {}", e))
+}
+
+/// 使用系统默认程序打开配置文件，没有关联程序时打开所在目录
+///
+/// 外部编辑保存后，配置文件监听会发送 `config-file-changed` 事件通知前端刷新
+#[tauri::command]
+pub async fn open_config_in_editor(app: tauri::AppHandle) -> Result<(), String> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let config_path = get_config_path()?;
+    if !config_path.exists() {
+        return Err("配置文件不存在".to_string());
+    }
+
+    let opener = app.opener();
+    if opener
+        .open_path(config_path.to_string_lossy(), None::<&str>)
+        .is_ok()
+    {
+        return Ok(());
+    }
+
+    opener
+        .reveal_item_in_dir(&config_path)
+        .map_err(|e| format!("打开配置文件失败: {}", e))
+}
+
+/// 将点分隔的键路径（如 `uplinks.npmjs.url`）拆分为各级键名
+fn split_key_path(key_path: &str) -> Result<Vec<&str>, String> {
+    let segments: Vec<&str> = key_path.trim().split('.').collect();
+    if segments.iter().any(|s| s.is_empty()) {
+        return Err(format!("无效的键路径: {}", key_path));
+    }
+    Ok(segments)
+}
+
+/// 按点分隔的键路径读取配置项，返回 JSON 值（数组可用数字下标，如 `middlewares.audit` / `listen.0`）
+///
+/// 配置项不存在时返回 None
+#[tauri::command]
+pub async fn get_config_value(key_path: String) -> Result<Option<serde_json::Value>, String> {
+    let segments = split_key_path(&key_path)?;
+    let config = load_config()?;
+
+    let mut current = &config;
+    for segment in segments {
+        let next = match current {
+            Value::Mapping(mapping) => mapping.get(segment),
+            Value::Sequence(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        };
+        match next {
+            Some(value) => current = value,
+            None => return Ok(None),
+        }
+    }
+
+    serde_json::to_value(current)
+        .map(Some)
+        .map_err(|e| format!("转换配置项失败: {}", e))
+}
+
+/// 按点分隔的键路径设置标量配置项（字符串、数字、布尔值），传入 null 时移除该项
+///
+/// 中间缺失的层级会自动创建为映射；路径上已有非映射的值时拒绝修改
+#[tauri::command]
+pub async fn set_config_value(key_path: String, value: serde_json::Value) -> Result<(), String> {
+    let segments = split_key_path(&key_path)?;
+    if value.is_array() || value.is_object() {
+        return Err("仅支持设置字符串、数字或布尔值".to_string());
+    }
+    let (last, parents) = segments.split_last().ok_or("无效的键路径")?;
+
+    let mut config = load_config()?;
+    let mut current = config
+        .as_mapping_mut()
+        .ok_or_else(|| "配置文件格式无效".to_string())?;
+    for (depth, segment) in parents.iter().enumerate() {
+        current = current
+            .entry((*segment).into())
+            .or_insert_with(|| Value::Mapping(Mapping::new()))
+            .as_mapping_mut()
+            .ok_or_else(|| format!("{} 不是映射，无法设置子项", parents[..=depth].join(".")))?;
+    }
+
+    if value.is_null() {
+        current.shift_remove(*last);
+    } else {
+        let value = serde_yaml::to_value(&value).map_err(|e| format!("转换配置项失败: {}", e))?;
+        current.insert((*last).into(), value);
+    }
+
+    save_config(&config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_package_name_as_single_path_segment() {
+        assert_eq!(encode_path_segment("lodash.merge"), "lodash.merge");
+        assert_eq!(encode_path_segment("@scope/pkg"), "%40scope%2Fpkg");
+        assert_eq!(encode_path_segment("a b?#%"), "a%20b%3F%23%25");
+        assert_eq!(encode_path_segment("包"), "%E5%8C%85");
+    }
+
+    /// 默认配置中的 packages 映射
+    fn default_packages() -> Mapping {
+        let config: Value = serde_yaml::from_str(crate::tools::verdaccio::DEFAULT_CONFIG).unwrap();
+        config["packages"].as_mapping().unwrap().clone()
+    }
+
+    fn rule_patterns(packages: &Mapping) -> Vec<&str> {
+        packages.keys().filter_map(|k| k.as_str()).collect()
+    }
+
+    #[test]
+    fn inserts_scoped_rule_before_scope_wildcard() {
+        let mut packages = default_packages();
+        insert_package_rule(&mut packages, "@myorg/*".into(), Mapping::new());
+        assert_eq!(rule_patterns(&packages), ["local-*", "@myorg/*", "@*/*", "**"]);
+    }
+
+    #[test]
+    fn inserts_unscoped_rule_before_catch_all() {
+        let mut packages = default_packages();
+        insert_package_rule(&mut packages, "react-*".into(), Mapping::new());
+        assert_eq!(rule_patterns(&packages), ["local-*", "@*/*", "react-*", "**"]);
+
+        insert_package_rule(&mut packages, "local-tools".into(), Mapping::new());
+        assert_eq!(
+            rule_patterns(&packages),
+            ["local-tools", "local-*", "@*/*", "react-*", "**"]
+        );
+    }
+
+    #[test]
+    fn appends_rule_when_nothing_shadows_it() {
+        let mut packages: Mapping = serde_yaml::from_str("'@*/*': {}").unwrap();
+        insert_package_rule(&mut packages, "lodash".into(), Mapping::new());
+        assert_eq!(rule_patterns(&packages), ["@*/*", "lodash"]);
+    }
+
+    fn rule(pattern: &str) -> PackageRule {
+        PackageRule {
+            pattern: pattern.to_string(),
+            access: None,
+            publish: None,
+            proxy: None,
+        }
+    }
+
+    fn matches(pattern: &str, name: &str) -> bool {
+        package_pattern_regex(pattern).unwrap().is_match(name)
+    }
+
+    #[test]
+    fn default_rule_patterns_match_like_minimatch() {
+        assert!(matches("@*/*", "@scope/pkg"));
+        assert!(!matches("@*/*", "pkg"));
+        assert!(!matches("@*/*", "@scope/pkg/extra"));
+
+        assert!(matches("**", "pkg"));
+        assert!(matches("**", "@scope/pkg"));
+
+        assert!(matches("*", "pkg"));
+        assert!(!matches("*", "@scope/pkg"));
+
+        assert!(matches("local-*", "local-tools"));
+        assert!(!matches("local-*", "my-local-tools"));
+        assert!(matches("@myorg/pkg-?", "@myorg/pkg-a"));
+        assert!(!matches("lodash.merge", "lodashXmerge"));
+    }
+
+    #[test]
+    fn supports_braces_and_character_classes() {
+        assert!(matches("@{a,b}/*", "@a/pkg"));
+        assert!(matches("@{a,b}/*", "@b/pkg"));
+        assert!(!matches("@{a,b}/*", "@c/pkg"));
+
+        assert!(matches("pkg-[ab]", "pkg-a"));
+        assert!(!matches("pkg-[ab]", "pkg-c"));
+        assert!(matches("pkg-[!ab]", "pkg-c"));
+    }
+
+    #[test]
+    fn rejects_unsupported_patterns() {
+        assert!(package_pattern_regex("!@scope/*").is_err());
+        assert!(package_pattern_regex("@{a,b/*").is_err());
+        assert!(package_pattern_regex("pkg-[ab").is_err());
+    }
+
+    #[test]
+    fn skips_invalid_rules_when_matching() {
+        let rules = vec![rule("!@scope/*"), rule("@*/*"), rule("**")];
+        assert_eq!(
+            first_matching_rule(rules.clone(), "@scope/pkg").map(|r| r.pattern).as_deref(),
+            Some("@*/*")
+        );
+        assert_eq!(
+            first_matching_rule(rules, "pkg").map(|r| r.pattern).as_deref(),
+            Some("**")
+        );
+    }
+}
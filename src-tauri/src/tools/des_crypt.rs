@@ -0,0 +1,192 @@
+//! 传统 DES crypt（`crypt(3)`）实现，仅用于校验旧版 htpasswd 中的密码哈希
+
+/// crypt 系列哈希使用的 base64 字符表
+pub(crate) const CRYPT_ALPHABET: &[u8] = b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+const IP: [u8; 64] = [
+    58, 50, 42, 34, 26, 18, 10, 2, 60, 52, 44, 36, 28, 20, 12, 4, 62, 54, 46, 38, 30, 22, 14, 6,
+    64, 56, 48, 40, 32, 24, 16, 8, 57, 49, 41, 33, 25, 17, 9, 1, 59, 51, 43, 35, 27, 19, 11, 3,
+    61, 53, 45, 37, 29, 21, 13, 5, 63, 55, 47, 39, 31, 23, 15, 7,
+];
+
+const FP: [u8; 64] = [
+    40, 8, 48, 16, 56, 24, 64, 32, 39, 7, 47, 15, 55, 23, 63, 31, 38, 6, 46, 14, 54, 22, 62, 30,
+    37, 5, 45, 13, 53, 21, 61, 29, 36, 4, 44, 12, 52, 20, 60, 28, 35, 3, 43, 11, 51, 19, 59, 27,
+    34, 2, 42, 10, 50, 18, 58, 26, 33, 1, 41, 9, 49, 17, 57, 25,
+];
+
+const E: [u8; 48] = [
+    32, 1, 2, 3, 4, 5, 4, 5, 6, 7, 8, 9, 8, 9, 10, 11, 12, 13, 12, 13, 14, 15, 16, 17, 16, 17, 18,
+    19, 20, 21, 20, 21, 22, 23, 24, 25, 24, 25, 26, 27, 28, 29, 28, 29, 30, 31, 32, 1,
+];
+
+const P: [u8; 32] = [
+    16, 7, 20, 21, 29, 12, 28, 17, 1, 15, 23, 26, 5, 18, 31, 10, 2, 8, 24, 14, 32, 27, 3, 9, 19,
+    13, 30, 6, 22, 11, 4, 25,
+];
+
+const PC1: [u8; 56] = [
+    57, 49, 41, 33, 25, 17, 9, 1, 58, 50, 42, 34, 26, 18, 10, 2, 59, 51, 43, 35, 27, 19, 11, 3,
+    60, 52, 44, 36, 63, 55, 47, 39, 31, 23, 15, 7, 62, 54, 46, 38, 30, 22, 14, 6, 61, 53, 45, 37,
+    29, 21, 13, 5, 28, 20, 12, 4,
+];
+
+const PC2: [u8; 48] = [
+    14, 17, 11, 24, 1, 5, 3, 28, 15, 6, 21, 10, 23, 19, 12, 4, 26, 8, 16, 7, 27, 20, 13, 2, 41,
+    52, 31, 37, 47, 55, 30, 40, 51, 45, 33, 48, 44, 49, 39, 56, 34, 53, 46, 42, 50, 36, 29, 32,
+];
+
+const SHIFTS: [u8; 16] = [1, 1, 2, 2, 2, 2, 2, 2, 1, 2, 2, 2, 2, 2, 2, 1];
+
+const S: [[u8; 64]; 8] = [
+    [
+        14, 4, 13, 1, 2, 15, 11, 8, 3, 10, 6, 12, 5, 9, 0, 7, 0, 15, 7, 4, 14, 2, 13, 1, 10, 6,
+        12, 11, 9, 5, 3, 8, 4, 1, 14, 8, 13, 6, 2, 11, 15, 12, 9, 7, 3, 10, 5, 0, 15, 12, 8, 2, 4,
+        9, 1, 7, 5, 11, 3, 14, 10, 0, 6, 13,
+    ],
+    [
+        15, 1, 8, 14, 6, 11, 3, 4, 9, 7, 2, 13, 12, 0, 5, 10, 3, 13, 4, 7, 15, 2, 8, 14, 12, 0, 1,
+        10, 6, 9, 11, 5, 0, 14, 7, 11, 10, 4, 13, 1, 5, 8, 12, 6, 9, 3, 2, 15, 13, 8, 10, 1, 3,
+        15, 4, 2, 11, 6, 7, 12, 0, 5, 14, 9,
+    ],
+    [
+        10, 0, 9, 14, 6, 3, 15, 5, 1, 13, 12, 7, 11, 4, 2, 8, 13, 7, 0, 9, 3, 4, 6, 10, 2, 8, 5,
+        14, 12, 11, 15, 1, 13, 6, 4, 9, 8, 15, 3, 0, 11, 1, 2, 12, 5, 10, 14, 7, 1, 10, 13, 0, 6,
+        9, 8, 7, 4, 15, 14, 3, 11, 5, 2, 12,
+    ],
+    [
+        7, 13, 14, 3, 0, 6, 9, 10, 1, 2, 8, 5, 11, 12, 4, 15, 13, 8, 11, 5, 6, 15, 0, 3, 4, 7, 2,
+        12, 1, 10, 14, 9, 10, 6, 9, 0, 12, 11, 7, 13, 15, 1, 3, 14, 5, 2, 8, 4, 3, 15, 0, 6, 10,
+        1, 13, 8, 9, 4, 5, 11, 12, 7, 2, 14,
+    ],
+    [
+        2, 12, 4, 1, 7, 10, 11, 6, 8, 5, 3, 15, 13, 0, 14, 9, 14, 11, 2, 12, 4, 7, 13, 1, 5, 0,
+        15, 10, 3, 9, 8, 6, 4, 2, 1, 11, 10, 13, 7, 8, 15, 9, 12, 5, 6, 3, 0, 14, 11, 8, 12, 7, 1,
+        14, 2, 13, 6, 15, 0, 9, 10, 4, 5, 3,
+    ],
+    [
+        12, 1, 10, 15, 9, 2, 6, 8, 0, 13, 3, 4, 14, 7, 5, 11, 10, 15, 4, 2, 7, 12, 9, 5, 6, 1, 13,
+        14, 0, 11, 3, 8, 9, 14, 15, 5, 2, 8, 12, 3, 7, 0, 4, 10, 1, 13, 11, 6, 4, 3, 2, 12, 9, 5,
+        15, 10, 11, 14, 1, 7, 6, 0, 8, 13,
+    ],
+    [
+        4, 11, 2, 14, 15, 0, 8, 13, 3, 12, 9, 7, 5, 10, 6, 1, 13, 0, 11, 7, 4, 9, 1, 10, 14, 3, 5,
+        12, 2, 15, 8, 6, 1, 4, 11, 13, 12, 3, 7, 14, 10, 15, 6, 8, 0, 5, 9, 2, 6, 11, 13, 8, 1, 4,
+        10, 7, 9, 5, 0, 15, 14, 2, 3, 12,
+    ],
+    [
+        13, 2, 8, 4, 6, 15, 11, 1, 10, 9, 3, 14, 5, 0, 12, 7, 1, 15, 13, 8, 10, 3, 7, 4, 12, 5, 6,
+        11, 0, 14, 9, 2, 7, 11, 4, 1, 9, 12, 14, 2, 0, 6, 10, 13, 15, 3, 5, 8, 2, 1, 14, 7, 4, 10,
+        8, 13, 15, 12, 9, 0, 3, 5, 6, 11,
+    ],
+];
+
+/// 按置换表（位序号从 1 开始）从输入位数组中取位
+fn permute<const N: usize>(input: &[u8], table: &[u8; N]) -> [u8; N] {
+    let mut output = [0u8; N];
+    for (out, &pos) in output.iter_mut().zip(table) {
+        *out = input[pos as usize - 1];
+    }
+    output
+}
+
+/// 由 64 位密钥生成 16 轮子密钥
+fn key_schedule(key: &[u8; 64]) -> [[u8; 48]; 16] {
+    let mut cd = permute(key, &PC1);
+    let mut subkeys = [[0u8; 48]; 16];
+    for (subkey, &shift) in subkeys.iter_mut().zip(&SHIFTS) {
+        cd[..28].rotate_left(shift as usize);
+        cd[28..].rotate_left(shift as usize);
+        *subkey = permute(&cd, &PC2);
+    }
+    subkeys
+}
+
+/// 使用给定的 E 扩展表加密一个 64 位分组（crypt 通过 salt 交换 E 表中的位）
+fn encrypt_block(block: &[u8; 64], subkeys: &[[u8; 48]; 16], e_table: &[u8; 48]) -> [u8; 64] {
+    let permuted = permute(block, &IP);
+    let (mut left, mut right) = ([0u8; 32], [0u8; 32]);
+    left.copy_from_slice(&permuted[..32]);
+    right.copy_from_slice(&permuted[32..]);
+
+    for subkey in subkeys {
+        let mut expanded = permute(&right, e_table);
+        for (bit, key_bit) in expanded.iter_mut().zip(subkey) {
+            *bit ^= key_bit;
+        }
+        let mut substituted = [0u8; 32];
+        for (i, chunk) in expanded.chunks(6).enumerate() {
+            let row = (chunk[0] << 1) | chunk[5];
+            let col = (chunk[1] << 3) | (chunk[2] << 2) | (chunk[3] << 1) | chunk[4];
+            let value = S[i][(row * 16 + col) as usize];
+            for j in 0..4 {
+                substituted[i * 4 + j] = (value >> (3 - j)) & 1;
+            }
+        }
+        let f = permute(&substituted, &P);
+        let mut next_right = [0u8; 32];
+        for (i, bit) in next_right.iter_mut().enumerate() {
+            *bit = left[i] ^ f[i];
+        }
+        left = right;
+        right = next_right;
+    }
+
+    let mut preoutput = [0u8; 64];
+    preoutput[..32].copy_from_slice(&right);
+    preoutput[32..].copy_from_slice(&left);
+    permute(&preoutput, &FP)
+}
+
+/// 计算传统 DES crypt 哈希（2 位 salt + 11 位哈希，密码只取前 8 个字符）
+pub(crate) fn des_crypt(password: &str, salt: &str) -> Option<String> {
+    let salt = salt.as_bytes().get(..2)?;
+    let mut e_table = E;
+    for (i, &c) in salt.iter().enumerate() {
+        let value = CRYPT_ALPHABET.iter().position(|&a| a == c)?;
+        for j in 0..6 {
+            if (value >> j) & 1 == 1 {
+                e_table.swap(6 * i + j, 6 * i + j + 24);
+            }
+        }
+    }
+
+    let mut key = [0u8; 64];
+    for (i, &c) in password.as_bytes().iter().take(8).enumerate() {
+        for j in 0..7 {
+            key[i * 8 + j] = (c >> (6 - j)) & 1;
+        }
+    }
+    let subkeys = key_schedule(&key);
+
+    let mut block = [0u8; 64];
+    for _ in 0..25 {
+        block = encrypt_block(&block, &subkeys, &e_table);
+    }
+
+    let mut encoded = String::with_capacity(13);
+    encoded.push(salt[0] as char);
+    encoded.push(salt[1] as char);
+    let bits: Vec<u8> = block.iter().copied().chain([0, 0]).collect();
+    for chunk in bits.chunks(6) {
+        let value = chunk.iter().fold(0usize, |acc, &bit| (acc << 1) | bit as usize);
+        encoded.push(CRYPT_ALPHABET[value] as char);
+    }
+    Some(encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn des_crypt_matches_system_crypt() {
+        assert_eq!(des_crypt("password", "ab").as_deref(), Some("abJnggxhB/yWI"));
+    }
+
+    #[test]
+    fn des_crypt_rejects_invalid_salt() {
+        assert_eq!(des_crypt("password", "a"), None);
+        assert_eq!(des_crypt("password", "a!"), None);
+    }
+}
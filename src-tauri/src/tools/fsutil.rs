@@ -0,0 +1,84 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// 获取数据根目录（用户主目录）
+///
+/// 无法确定主目录时返回错误，而不是回退到当前工作目录，避免把 `.verdaccio` 等数据写到意料之外的位置
+pub(crate) fn resolve_data_root() -> Result<PathBuf, String> {
+    data_root_from(dirs::home_dir())
+}
+
+/// 根据查找到的主目录确定数据根目录
+fn data_root_from(home: Option<PathBuf>) -> Result<PathBuf, String> {
+    home.ok_or_else(|| "无法确定主目录".to_string())
+}
+
+/// 原子写入文件：先写入同目录下的临时文件并刷盘，再重命名覆盖目标文件
+///
+/// 写入过程中崩溃或出错时，目标文件保持原样，不会出现只写了一半的内容
+pub(crate) fn atomic_write(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let file_name = path.file_name().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, "无效的文件路径")
+    })?;
+    let tmp_path = dir.join(format!(
+        ".{}.{}.tmp",
+        file_name.to_string_lossy(),
+        std::process::id()
+    ));
+
+    let write_tmp = || -> std::io::Result<()> {
+        let mut file = std::fs::File::create(&tmp_path)?;
+        file.write_all(contents.as_ref())?;
+        file.sync_all()
+    };
+
+    if let Err(e) = write_tmp().and_then(|_| std::fs::rename(&tmp_path, path)) {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_root_errors_without_home_dir() {
+        assert_eq!(data_root_from(None), Err("无法确定主目录".to_string()));
+        assert_eq!(
+            data_root_from(Some(PathBuf::from("/home/user"))),
+            Ok(PathBuf::from("/home/user"))
+        );
+    }
+
+    #[test]
+    fn atomic_write_replaces_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("settings.json");
+        std::fs::write(&target, "old").unwrap();
+
+        atomic_write(&target, "new").unwrap();
+
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "new");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn atomic_write_keeps_target_when_temp_write_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("settings.json");
+        std::fs::write(&target, "old").unwrap();
+
+        // 在临时文件路径上放一个目录，使创建临时文件失败
+        let tmp_path = dir
+            .path()
+            .join(format!(".settings.json.{}.tmp", std::process::id()));
+        std::fs::create_dir(&tmp_path).unwrap();
+
+        assert!(atomic_write(&target, "new").is_err());
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "old");
+    }
+}
@@ -2,8 +2,22 @@ pub mod verdaccio;
 pub mod packages;
 pub mod settings;
 pub mod users;
+pub mod config;
+pub mod storage;
+pub mod update;
+pub mod fsutil;
+pub mod watcher;
+pub mod bundle;
+pub mod diagnostics;
+pub mod des_crypt;
 
 pub use verdaccio::*;
 pub use packages::*;
 pub use settings::*;
 pub use users::*;
+pub use config::*;
+pub use storage::*;
+pub use update::*;
+pub use watcher::*;
+pub use bundle::*;
+pub use diagnostics::*;
@@ -1,5 +1,17 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::State;
+
+use super::config::{load_config, permission_values};
+use super::fsutil::{atomic_write, resolve_data_root};
+use super::settings::{http_client_builder, load_app_settings};
+use super::users::get_users;
+use super::verdaccio::VerdaccioProcess;
+
+/// Verdaccio API 不可用（未启动或请求超时）时返回的错误前缀，前端据此提示启动服务后再按类型筛选
+pub const API_UNAVAILABLE_ERROR: &str = "ApiUnavailable";
 
 /// 包类型过滤
 #[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
@@ -23,6 +35,27 @@ pub struct PaginatedResult<T> {
     pub total_pages: usize,
 }
 
+/// 各类型包数量
+#[derive(Debug, Clone, Serialize)]
+pub struct PackageCounts {
+    pub private: usize,
+    pub cached: usize,
+    pub all: usize,
+}
+
+/// 仓库统计信息（首页展示用）
+///
+/// 服务未运行（API 不可用）时无法区分私有与缓存包，`private` / `cached` 为 None
+#[derive(Debug, Clone, Serialize)]
+pub struct RegistryStats {
+    pub total_packages: usize,
+    pub private: Option<usize>,
+    pub cached: Option<usize>,
+    pub total_versions: usize,
+    pub storage_bytes: u64,
+    pub user_count: usize,
+}
+
 /// 包信息（前端使用）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PackageInfo {
@@ -37,6 +70,173 @@ pub struct PackageInfo {
     pub repository: Option<String>,
     pub created: Option<String>,
     pub modified: Option<String>,
+    /// created / modified 的来源（文档缺少时间字段时取自文件系统）
+    pub time_source: TimeSource,
+    /// 是否为以 `.` 开头的隐藏条目（Verdaccio 内部数据等）
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+/// 包时间信息来源
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeSource {
+    Document,
+    Filesystem,
+}
+
+/// 单个版本 tarball 的完整性校验结果
+#[derive(Debug, Clone, Serialize)]
+pub struct TarballIntegrity {
+    pub version: String,
+    /// tarball 是否存在于本地存储（缓存包通常只包含下载过的版本）
+    pub present: bool,
+    pub ok: bool,
+    /// 文档中记录的校验值（优先使用 sha512 integrity，否则使用 shasum）
+    pub expected: Option<String>,
+    /// 根据实际文件计算出的同类校验值
+    pub actual: Option<String>,
+}
+
+/// 导入 tarball 的结果
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportedTarball {
+    pub name: String,
+    pub version: String,
+}
+
+/// 重建包文档的结果
+#[derive(Debug, Clone, Serialize)]
+pub struct RebuiltMetadata {
+    /// 从 tarball 中恢复的版本（从新到旧）
+    pub versions: Vec<String>,
+    /// 无法读取或不属于该包而被跳过的 tarball 文件名
+    pub skipped: Vec<String>,
+}
+
+/// 依赖项
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyEntry {
+    pub name: String,
+    /// 依赖声明的版本范围
+    pub range: String,
+    /// 本地存储中是否存在该包（否则需要通过上游代理获取）
+    pub available_locally: bool,
+}
+
+/// 包某个版本的依赖信息
+#[derive(Debug, Clone, Serialize)]
+pub struct PackageDependencies {
+    pub version: String,
+    pub dependencies: Vec<DependencyEntry>,
+    pub dev_dependencies: Vec<DependencyEntry>,
+    pub peer_dependencies: Vec<DependencyEntry>,
+}
+
+/// 依赖某个包的包版本
+#[derive(Debug, Clone, Serialize)]
+pub struct DependentEntry {
+    pub package: String,
+    pub version: String,
+    /// 依赖声明的版本范围
+    pub dep_version: String,
+}
+
+/// 单个包可清理的旧版本
+#[derive(Debug, Clone, Serialize)]
+pub struct PrunablePackage {
+    pub package: String,
+    pub versions: Vec<String>,
+    pub reclaimable_bytes: u64,
+}
+
+/// 旧版本清理计划
+#[derive(Debug, Clone, Serialize)]
+pub struct PrunePlan {
+    pub packages: Vec<PrunablePackage>,
+    pub total_bytes: u64,
+}
+
+/// 旧版本清理结果
+#[derive(Debug, Clone, Serialize)]
+pub struct PruneResult {
+    /// 实际删除的版本
+    pub packages: Vec<PrunablePackage>,
+    pub total_bytes: u64,
+    /// 删除失败的版本（`name` 为 `包名@版本`）或包及原因
+    pub failed: Vec<DeleteFailure>,
+}
+
+/// 按名称批量删除的结果
+#[derive(Debug, Clone, Serialize)]
+pub struct DeletePackagesResult {
+    pub deleted: Vec<String>,
+    /// 删除失败的包及原因
+    pub failed: Vec<DeleteFailure>,
+}
+
+/// 删除失败的包
+#[derive(Debug, Clone, Serialize)]
+pub struct DeleteFailure {
+    pub name: String,
+    pub error: String,
+}
+
+/// 单个版本的 tarball 信息
+#[derive(Debug, Clone, Serialize)]
+pub struct TarballEntry {
+    pub version: String,
+    pub tarball_filename: String,
+    /// 本地文件大小（未缓存到本地时为 None）
+    pub size_bytes: Option<u64>,
+    pub shasum: Option<String>,
+    /// 通过本地服务下载的地址
+    pub url: String,
+}
+
+/// 包文档 `time` 中的一条发布记录
+#[derive(Debug, Clone, Serialize)]
+pub struct PublishHistoryEntry {
+    /// 版本号，或 `created` / `modified`
+    pub key: String,
+    /// ISO 8601 时间
+    pub timestamp: String,
+}
+
+/// 删除上游仓库的影响范围
+#[derive(Debug, Clone, Serialize)]
+pub struct UplinkImpact {
+    /// 通过 `proxy` 引用该上游的包规则模式
+    pub rules: Vec<String>,
+    /// 存储中经由该上游缓存的包数量（根据包文档的 `_uplinks` 判断）
+    pub cached_packages: usize,
+}
+
+/// 包清单导出格式
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Json,
+    Csv,
+}
+
+/// 包清单条目
+#[derive(Debug, Clone, Serialize)]
+pub struct InventoryEntry {
+    pub name: String,
+    pub latest_version: String,
+    pub version_count: usize,
+    pub size_bytes: u64,
+    /// 来源：private（私有发布）或 cached（上游缓存）
+    pub origin: String,
+    pub modified: Option<String>,
+}
+
+/// 导出进度
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportProgress {
+    pub current: usize,
+    pub total: usize,
 }
 
 /// Verdaccio API 返回的包信息（用于获取私有包名称列表）
@@ -46,9 +246,8 @@ struct VerdaccioPackageResponse {
 }
 
 /// 获取存储目录
-fn get_storage_path() -> PathBuf {
-    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    home.join(".verdaccio").join("storage")
+pub(crate) fn get_storage_path() -> Result<PathBuf, String> {
+    Ok(resolve_data_root()?.join(".verdaccio").join("storage"))
 }
 
 /// 判断目录是否为有效的包目录（包含 package.json）
@@ -57,7 +256,10 @@ fn is_valid_package_dir(path: &PathBuf) -> bool {
 }
 
 /// 遍历存储目录，收集所有包目录及其名称（已排序）
-fn collect_package_dirs(storage_path: &PathBuf) -> Result<Vec<(PathBuf, String)>, String> {
+///
+/// `include_hidden` 为 true 时同时收集以 `.` 开头的隐藏条目（如 `.verdaccio-db.json`），
+/// 这些条目不要求是有效的包目录
+fn collect_package_dirs(storage_path: &PathBuf, include_hidden: bool) -> Result<Vec<(PathBuf, String)>, String> {
     if !storage_path.exists() {
         return Ok(vec![]);
     }
@@ -71,7 +273,10 @@ fn collect_package_dirs(storage_path: &PathBuf) -> Result<Vec<(PathBuf, String)>
         let name = entry.file_name().to_string_lossy().to_string();
 
         // 跳过隐藏目录（非 scoped 包）
-        if name.starts_with('.') && !name.starts_with('@') {
+        if name.starts_with('.') {
+            if include_hidden {
+                result.push((path, name));
+            }
             continue;
         }
 
@@ -102,20 +307,93 @@ fn collect_package_dirs(storage_path: &PathBuf) -> Result<Vec<(PathBuf, String)>
     Ok(result)
 }
 
-/// 获取私有包名称列表（从 Verdaccio API 读取）
-async fn get_private_package_names(port: u16) -> Result<Vec<String>, String> {
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(5))
+/// 请求私有包列表的最大尝试次数
+const PRIVATE_PACKAGES_MAX_ATTEMPTS: u32 = 3;
+
+/// 请求私有包列表失败后的退避基准间隔（第 n 次重试等待 n 倍）
+const PRIVATE_PACKAGES_RETRY_BACKOFF: std::time::Duration = std::time::Duration::from_millis(300);
+
+/// 私有包名称列表缓存的有效期
+const PRIVATE_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// 私有包名称列表缓存：(获取时间, 端口, 私有包名称)
+///
+/// 界面快速切换类型筛选时复用最近一次的 API 结果；删除、导入等修改存储的操作后需调用
+/// [`PrivatePackageCache::invalidate`]
+#[derive(Default)]
+pub struct PrivatePackageCache(Mutex<Option<(Instant, u16, Vec<String>)>>);
+
+impl PrivatePackageCache {
+    /// 获取未过期的缓存（端口不一致时视为未命中）
+    fn get(&self, port: u16) -> Option<Vec<String>> {
+        let cache = self.0.lock().ok()?;
+        cache
+            .as_ref()
+            .filter(|(fetched_at, cached_port, _)| {
+                *cached_port == port && fetched_at.elapsed() < PRIVATE_CACHE_TTL
+            })
+            .map(|(_, _, names)| names.clone())
+    }
+
+    fn set(&self, port: u16, names: Vec<String>) {
+        if let Ok(mut cache) = self.0.lock() {
+            *cache = Some((Instant::now(), port, names));
+        }
+    }
+
+    /// 清除缓存，下次按类型筛选时重新请求 API
+    pub fn invalidate(&self) {
+        if let Ok(mut cache) = self.0.lock() {
+            *cache = None;
+        }
+    }
+}
+
+/// 获取私有包名称列表，优先使用未过期的缓存
+async fn get_private_package_names(cache: &PrivatePackageCache, port: u16) -> Result<Vec<String>, String> {
+    if let Some(names) = cache.get(port) {
+        return Ok(names);
+    }
+    let names = fetch_private_package_names(port).await?;
+    cache.set(port, names.clone());
+    Ok(names)
+}
+
+/// 从 Verdaccio API 读取私有包名称列表
+///
+/// 每次请求的超时时间取自 `AppSettings.api_timeout_ms`，连接失败、超时或非成功状态码时重试，
+/// 仍然连接失败或超时则返回 [`API_UNAVAILABLE_ERROR`] 错误
+async fn fetch_private_package_names(port: u16) -> Result<Vec<String>, String> {
+    let timeout_ms = load_app_settings()?.api_timeout_ms;
+    let client = http_client_builder()?
+        .timeout(std::time::Duration::from_millis(timeout_ms))
         .build()
         .map_err(|e| format!("创建 HTTP 客户端失败: {}", e))?;
 
     let url = format!("http://localhost:{}/-/verdaccio/data/packages", port);
 
-    let response = client
-        .get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("请求失败: {}", e))?;
+    // 服务刚启动时 API 可能短暂不可用，按退避间隔重试
+    let mut attempt = 1;
+    let response = loop {
+        let result = client.get(&url).send().await;
+        let retryable = match &result {
+            Ok(response) => !response.status().is_success(),
+            Err(e) => e.is_timeout() || e.is_connect(),
+        };
+        if !retryable || attempt >= PRIVATE_PACKAGES_MAX_ATTEMPTS {
+            break result;
+        }
+        tokio::time::sleep(PRIVATE_PACKAGES_RETRY_BACKOFF * attempt).await;
+        attempt += 1;
+    };
+
+    let response = response.map_err(|e| {
+        if e.is_timeout() || e.is_connect() {
+            format!("{}: Verdaccio API 不可用: {}", API_UNAVAILABLE_ERROR, e)
+        } else {
+            format!("请求失败: {}", e)
+        }
+    })?;
 
     if !response.status().is_success() {
         return Ok(vec![]);
@@ -130,30 +408,39 @@ async fn get_private_package_names(port: u16) -> Result<Vec<String>, String> {
 }
 
 /// 根据包类型过滤包名称列表
+///
+/// `All` 只依赖磁盘目录，不请求 API，服务未启动时也能返回完整列表
 async fn filter_package_names_by_type(
+    cache: &PrivatePackageCache,
     all_names: Vec<String>,
     package_type: PackageType,
     port: u16,
 ) -> Result<Vec<String>, String> {
     match package_type {
         PackageType::All => Ok(all_names),
-        PackageType::Private => {
-            let private_names = get_private_package_names(port).await?;
-            Ok(all_names
-                .into_iter()
-                .filter(|name| private_names.contains(name))
-                .collect())
-        }
-        PackageType::Cached => {
-            let private_names = get_private_package_names(port).await?;
-            Ok(all_names
-                .into_iter()
-                .filter(|name| !private_names.contains(name))
-                .collect())
+        _ => {
+            let private_names = get_private_package_names(cache, port).await?;
+            Ok(retain_package_type(all_names, package_type, &private_names))
         }
     }
 }
 
+/// 按私有包名称列表保留指定类型的包
+fn retain_package_type(
+    all_names: Vec<String>,
+    package_type: PackageType,
+    private_names: &[String],
+) -> Vec<String> {
+    all_names
+        .into_iter()
+        .filter(|name| match package_type {
+            PackageType::All => true,
+            PackageType::Private => private_names.contains(name),
+            PackageType::Cached => !private_names.contains(name),
+        })
+        .collect()
+}
+
 /// 从 package.json 读取包详情
 fn read_package_info(path: &PathBuf, name: &str) -> Option<PackageInfo> {
     let package_json_path = path.join("package.json");
@@ -174,17 +461,13 @@ fn read_package_info(path: &PathBuf, name: &str) -> Option<PackageInfo> {
         vec![]
     };
 
-    // 获取最新版本
-    let latest = json
-        .get("dist-tags")
-        .and_then(|dt| dt.get("latest"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("0.0.0");
+    // 获取最新版本（缺少 dist-tags.latest 时取最高版本）
+    let latest = latest_version(&json).unwrap_or_else(|| "0.0.0".to_string());
 
     // 获取最新版本的详细信息
     let latest_info = json
         .get("versions")
-        .and_then(|v| v.get(latest));
+        .and_then(|v| v.get(&latest));
 
     // 解析 author 字段（可能是字符串或对象）
     let author = latest_info
@@ -236,9 +519,42 @@ fn read_package_info(path: &PathBuf, name: &str) -> Option<PackageInfo> {
                 .map(|s| s.to_string())
         });
 
+    let document_time = |key: &str| {
+        json.get("time")
+            .and_then(|t| t.get(key))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    };
+    let mut created = document_time("created");
+    let mut modified = document_time("modified");
+
+    // 缓存包的文档可能没有时间字段，回退到文件系统时间以保证界面可以排序
+    let mut time_source = TimeSource::Document;
+    if created.is_none() || modified.is_none() {
+        let to_rfc3339 = |time: std::time::SystemTime| {
+            chrono::DateTime::<chrono::Utc>::from(time).to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+        };
+        let dir_metadata = std::fs::metadata(path).ok();
+        let file_modified = std::fs::metadata(&package_json_path)
+            .and_then(|m| m.modified())
+            .ok()
+            .map(to_rfc3339);
+
+        if created.is_none() {
+            created = dir_metadata
+                .as_ref()
+                .and_then(|m| m.created().or_else(|_| m.modified()).ok())
+                .map(to_rfc3339);
+        }
+        if modified.is_none() {
+            modified = file_modified;
+        }
+        time_source = TimeSource::Filesystem;
+    }
+
     Some(PackageInfo {
         name: name.to_string(),
-        version: latest.to_string(),
+        version: latest,
         description,
         author,
         license,
@@ -246,19 +562,39 @@ fn read_package_info(path: &PathBuf, name: &str) -> Option<PackageInfo> {
         keywords,
         homepage,
         repository,
-        created: json
-            .get("time")
-            .and_then(|t| t.get("created"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string()),
-        modified: json
-            .get("time")
-            .and_then(|t| t.get("modified"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string()),
+        created,
+        modified,
+        time_source,
+        hidden: name.starts_with('.'),
     })
 }
 
+/// 为无法解析为包的隐藏条目生成基本信息（仅包含名称与文件系统时间）
+fn hidden_entry_info(path: &Path, name: &str) -> PackageInfo {
+    let modified = std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .map(|time| {
+            chrono::DateTime::<chrono::Utc>::from(time).to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+        });
+
+    PackageInfo {
+        name: name.to_string(),
+        version: String::new(),
+        description: None,
+        author: None,
+        license: None,
+        versions: vec![],
+        keywords: vec![],
+        homepage: None,
+        repository: None,
+        created: None,
+        modified,
+        time_source: TimeSource::Filesystem,
+        hidden: true,
+    }
+}
+
 /// 解析 author 字段
 fn parse_author(value: &serde_json::Value) -> Option<String> {
     if let Some(s) = value.as_str() {
@@ -281,16 +617,41 @@ fn parse_repository(value: &serde_json::Value) -> Option<String> {
     }
 }
 
-/// 简单的版本比较（用于排序）
-fn version_compare(a: &str, b: &str) -> std::cmp::Ordering {
-    let parse_version = |v: &str| -> Vec<u32> {
-        v.split(|c: char| !c.is_ascii_digit())
-            .filter_map(|s| s.parse().ok())
-            .collect()
+/// 版本比较（用于排序）
+///
+/// 合法 semver 按 semver 规则比较（预发布版本低于正式版本），无法解析的版本始终低于合法版本，
+/// 彼此之间按数字段比较；结果相同时再按原始字符串比较，保证是全序关系
+pub(crate) fn version_compare(a: &str, b: &str) -> std::cmp::Ordering {
+    let ordering = match (semver::Version::parse(a), semver::Version::parse(b)) {
+        (Ok(va), Ok(vb)) => va.cmp(&vb),
+        (Ok(_), Err(_)) => std::cmp::Ordering::Greater,
+        (Err(_), Ok(_)) => std::cmp::Ordering::Less,
+        (Err(_), Err(_)) => {
+            let parse_version = |v: &str| -> Vec<u32> {
+                v.split(|c: char| !c.is_ascii_digit())
+                    .filter_map(|s| s.parse().ok())
+                    .collect()
+            };
+            parse_version(a).cmp(&parse_version(b))
+        }
     };
-    let va = parse_version(a);
-    let vb = parse_version(b);
-    va.cmp(&vb)
+    ordering.then_with(|| a.cmp(b))
+}
+
+/// 删除包目录前确认其（解析符号链接和 `..` 后）位于存储目录内，防止越界删除
+fn remove_package_dir(storage_path: &Path, package_path: &Path) -> Result<(), String> {
+    let storage_root = storage_path
+        .canonicalize()
+        .map_err(|e| format!("解析存储目录失败: {}", e))?;
+    let target = package_path
+        .canonicalize()
+        .map_err(|e| format!("解析包路径失败: {}", e))?;
+
+    if target == storage_root || !target.starts_with(&storage_root) {
+        return Err(format!("包路径不在存储目录内: {}", package_path.display()));
+    }
+
+    std::fs::remove_dir_all(&target).map_err(|e| format!("删除包失败: {}", e))
 }
 
 /// 根据包名获取包路径
@@ -307,24 +668,451 @@ fn get_package_path(storage_path: &PathBuf, package_name: &str) -> PathBuf {
     }
 }
 
+/// 读取包目录中的 package.json 文档
+fn read_package_document(package_path: &Path) -> Result<serde_json::Value, String> {
+    let content = std::fs::read_to_string(package_path.join("package.json"))
+        .map_err(|e| format!("读取 package.json 失败: {}", e))?;
+
+    serde_json::from_str(&content).map_err(|e| format!("解析 package.json 失败: {}", e))
+}
+
+/// 获取版本对应的 tarball 文件名（优先取 dist.tarball 的文件名部分）
+fn tarball_filename(package_name: &str, version: &str, version_info: &serde_json::Value) -> String {
+    version_info
+        .get("dist")
+        .and_then(|d| d.get("tarball"))
+        .and_then(|t| t.as_str())
+        .and_then(|url| url.rsplit('/').next())
+        .filter(|name| !name.is_empty())
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| format!("{}-{}.tgz", unscoped_name(package_name), version))
+}
+
+/// 根据包文档判断是否为从上游代理缓存的包
+///
+/// 缓存包的 `_uplinks` 记录了来源上游，且每个版本的 tarball 都登记在 `_distfiles` 中；
+/// 本地发布的版本不会出现在 `_distfiles` 中，无法确定时一律视为私有包
+fn is_uplink_cache_document(package_name: &str, document: &serde_json::Value) -> bool {
+    let has_uplinks = document
+        .get("_uplinks")
+        .and_then(|u| u.as_object())
+        .is_some_and(|u| !u.is_empty());
+    let Some(distfiles) = document.get("_distfiles").and_then(|d| d.as_object()) else {
+        return false;
+    };
+    let Some(versions) = document.get("versions").and_then(|v| v.as_object()) else {
+        return false;
+    };
+
+    has_uplinks
+        && versions
+            .iter()
+            .all(|(version, info)| distfiles.contains_key(&tarball_filename(package_name, version, info)))
+}
+
+/// 计算 sha1 十六进制摘要（对应 dist.shasum）
+fn sha1_hex(data: &[u8]) -> String {
+    use sha1::{Digest, Sha1};
+    format!("{:x}", Sha1::digest(data))
+}
+
+/// 计算 sha512 SSRI 字符串（对应 dist.integrity）
+fn sha512_integrity(data: &[u8]) -> String {
+    use base64::Engine;
+    use sha2::{Digest, Sha512};
+    format!(
+        "sha512-{}",
+        base64::engine::general_purpose::STANDARD.encode(Sha512::digest(data))
+    )
+}
+
+/// 校验单个版本的 tarball
+fn verify_tarball(
+    package_path: &Path,
+    package_name: &str,
+    version: &str,
+    version_info: &serde_json::Value,
+) -> TarballIntegrity {
+    let dist = version_info.get("dist");
+    let shasum = dist
+        .and_then(|d| d.get("shasum"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_lowercase());
+    let integrity = dist
+        .and_then(|d| d.get("integrity"))
+        .and_then(|v| v.as_str())
+        .and_then(|i| i.split_whitespace().find(|h| h.starts_with("sha512-")))
+        .map(|s| s.to_string());
+
+    let tarball_path = package_path.join(tarball_filename(package_name, version, version_info));
+    let data = std::fs::read(&tarball_path).ok();
+
+    let (expected, actual) = match (&integrity, &shasum) {
+        (Some(expected), _) => (Some(expected.clone()), data.as_deref().map(sha512_integrity)),
+        (None, Some(expected)) => (Some(expected.clone()), data.as_deref().map(sha1_hex)),
+        (None, None) => (None, None),
+    };
+
+    TarballIntegrity {
+        version: version.to_string(),
+        present: data.is_some(),
+        ok: expected.is_some() && expected == actual,
+        expected,
+        actual,
+    }
+}
+
+/// 从 npm tarball 中读取 package/package.json
+fn read_tarball_manifest(data: &[u8]) -> Result<serde_json::Value, String> {
+    let decoder = flate2::read::GzDecoder::new(data);
+    let mut archive = tar::Archive::new(decoder);
+    let entries = archive
+        .entries()
+        .map_err(|e| format!("读取 tarball 失败: {}", e))?;
+
+    for entry in entries {
+        let mut entry = entry.map_err(|e| format!("读取 tarball 失败: {}", e))?;
+        let path = entry
+            .path()
+            .map_err(|e| format!("读取 tarball 失败: {}", e))?
+            .to_string_lossy()
+            .to_string();
+
+        // npm 打包时根目录通常为 package/，但并非强制
+        if path.matches('/').count() == 1 && path.ends_with("/package.json") {
+            let mut content = String::new();
+            std::io::Read::read_to_string(&mut entry, &mut content)
+                .map_err(|e| format!("读取 package.json 失败: {}", e))?;
+            return serde_json::from_str(&content)
+                .map_err(|e| format!("解析 package.json 失败: {}", e));
+        }
+    }
+
+    Err("tarball 中未找到 package.json".to_string())
+}
+
+/// 将版本信息中的依赖映射转换为依赖项列表（按名称排序）
+fn parse_dependency_map(
+    version_info: &serde_json::Value,
+    key: &str,
+    local_names: &std::collections::HashSet<String>,
+) -> Vec<DependencyEntry> {
+    let Some(deps) = version_info.get(key).and_then(|d| d.as_object()) else {
+        return vec![];
+    };
+
+    let mut entries: Vec<DependencyEntry> = deps
+        .iter()
+        .map(|(name, range)| DependencyEntry {
+            name: name.clone(),
+            range: range.as_str().unwrap_or_default().to_string(),
+            available_locally: local_names.contains(name),
+        })
+        .collect();
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    entries
+}
+
+/// 将 npm 风格的版本范围（空格表示“且”，`||` 表示“或”）解析为 semver 范围列表
+fn parse_npm_range(range: &str) -> Result<Vec<semver::VersionReq>, String> {
+    range
+        .split("||")
+        .map(|part| {
+            let comparators = part.split_whitespace().collect::<Vec<_>>().join(", ");
+            let comparators = if comparators.is_empty() { "*".to_string() } else { comparators };
+            semver::VersionReq::parse(&comparators)
+                .map_err(|e| format!("无效的版本范围 {}: {}", range, e))
+        })
+        .collect()
+}
+
+/// 判断依赖声明是否落在指定范围内（取声明中的基础版本比较，如 `^1.2.3` 取 1.2.3）
+fn dependency_matches_range(dep_version: &str, ranges: &[semver::VersionReq]) -> bool {
+    let base = dep_version
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches(['^', '~', '=', '>', '<', 'v']);
+    semver::Version::parse(base).is_ok_and(|version| ranges.iter().any(|r| r.matches(&version)))
+}
+
+/// 获取包文档的 latest 版本
+///
+/// 缺少 dist-tags.latest 时取最高的正式版本，没有正式版本时取最高的预发布版本
+fn latest_version(document: &serde_json::Value) -> Option<String> {
+    if let Some(latest) = document
+        .get("dist-tags")
+        .and_then(|t| t.get("latest"))
+        .and_then(|v| v.as_str())
+    {
+        return Some(latest.to_string());
+    }
+
+    let versions = document.get("versions").and_then(|v| v.as_object())?;
+    let is_prerelease = |v: &str| semver::Version::parse(v).is_ok_and(|v| !v.pre.is_empty());
+    versions
+        .keys()
+        .filter(|v| !is_prerelease(v))
+        .max_by(|a, b| version_compare(a, b))
+        .or_else(|| versions.keys().max_by(|a, b| version_compare(a, b)))
+        .cloned()
+}
+
+/// 计算包中可清理的旧版本：保留最新的 `keep_latest` 个版本以及被 dist-tags 引用的版本
+fn prunable_versions(document: &serde_json::Value, keep_latest: usize) -> Vec<String> {
+    let Some(versions) = document.get("versions").and_then(|v| v.as_object()) else {
+        return vec![];
+    };
+    let tagged: std::collections::HashSet<&str> = document
+        .get("dist-tags")
+        .and_then(|t| t.as_object())
+        .map(|tags| tags.values().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
+
+    let mut sorted: Vec<&String> = versions.keys().collect();
+    sorted.sort_by(|a, b| version_compare(b, a));
+
+    sorted
+        .into_iter()
+        .skip(keep_latest)
+        .filter(|v| !tagged.contains(v.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// 从包文档中移除一个版本并删除其 tarball，返回释放的字节数
+fn remove_package_version(
+    package_path: &Path,
+    package_name: &str,
+    document: &mut serde_json::Value,
+    version: &str,
+) -> Result<u64, String> {
+    let version_info = document
+        .get("versions")
+        .and_then(|v| v.get(version))
+        .ok_or_else(|| format!("版本 {} 不存在", version))?;
+    let filename = tarball_filename(package_name, version, version_info);
+    let doc = document
+        .as_object_mut()
+        .ok_or_else(|| "package.json 格式无效".to_string())?;
+
+    // 先删除 tarball，删除失败时文档中仍保留该版本
+    let tarball_path = package_path.join(&filename);
+    let size = std::fs::metadata(&tarball_path).map(|m| m.len()).unwrap_or(0);
+    if tarball_path.exists() {
+        std::fs::remove_file(&tarball_path).map_err(|e| format!("删除 {} 失败: {}", filename, e))?;
+    }
+
+    for (key, entry) in [("versions", version), ("time", version), ("_attachments", filename.as_str())] {
+        if let Some(map) = doc.get_mut(key).and_then(|v| v.as_object_mut()) {
+            map.remove(entry);
+        }
+    }
+
+    Ok(size)
+}
+
+/// 生成旧版本清理计划（`include_private` 为 false 时只包含根据包文档确认为上游缓存的包）
+///
+/// 无法确认是否为缓存包的包一律按私有包跳过，避免误删已发布的版本
+fn build_prune_plan(keep_latest: usize, include_private: bool) -> Result<PrunePlan, String> {
+    if keep_latest == 0 {
+        return Err("至少需要保留 1 个版本".to_string());
+    }
+
+    let mut packages = Vec::new();
+    for (path, name) in collect_package_dirs(&get_storage_path()?, false)? {
+        let Ok(document) = read_package_document(&path) else {
+            continue;
+        };
+        if !include_private && !is_uplink_cache_document(&name, &document) {
+            continue;
+        }
+        let versions = prunable_versions(&document, keep_latest);
+        if versions.is_empty() {
+            continue;
+        }
+        let reclaimable_bytes = versions
+            .iter()
+            .filter_map(|v| document.get("versions").and_then(|vs| vs.get(v)).map(|info| (v, info)))
+            .filter_map(|(v, info)| std::fs::metadata(path.join(tarball_filename(&name, v, info))).ok())
+            .map(|m| m.len())
+            .sum();
+        packages.push(PrunablePackage {
+            package: name,
+            versions,
+            reclaimable_bytes,
+        });
+    }
+
+    let total_bytes = packages.iter().map(|p| p.reclaimable_bytes).sum();
+    Ok(PrunePlan { packages, total_bytes })
+}
+
+/// npm 包名的最大长度
+const MAX_PACKAGE_NAME_LENGTH: usize = 214;
+
+/// 校验包名是否符合 npm 命名规则，并拒绝可能逃逸存储目录的名称
+///
+/// 只允许 `name` 或 `@scope/name` 两种形式；为兼容历史缓存包，不限制大写字母
+fn validate_package_name(name: &str) -> Result<(), String> {
+    let invalid = || Err(format!("无效的包名: {}", name));
+
+    if name.is_empty() || name.len() > MAX_PACKAGE_NAME_LENGTH || name.trim() != name {
+        return invalid();
+    }
+
+    let parts: Vec<&str> = match name.strip_prefix('@') {
+        Some(scoped) => match scoped.split_once('/') {
+            Some((scope, package)) => vec![scope, package],
+            None => return invalid(),
+        },
+        None => vec![name],
+    };
+
+    for part in parts {
+        if part.is_empty()
+            || part.starts_with('.')
+            || part.starts_with('_')
+            || part.contains("..")
+            || part
+                .chars()
+                .any(|c| c.is_whitespace() || c.is_control() || matches!(c, '/' | '\\' | ':' | '%'))
+        {
+            return invalid();
+        }
+    }
+
+    Ok(())
+}
+
+/// 获取包名去掉 scope 后的部分（tarball 文件名使用该部分）
+fn unscoped_name(package_name: &str) -> &str {
+    package_name.rsplit('/').next().unwrap_or(package_name)
+}
+
+/// 将包文档中的包名引用改写为新名称，返回需要重命名的 tarball 文件（旧文件名, 新文件名）
+fn rename_package_document(
+    document: &mut serde_json::Value,
+    old_name: &str,
+    new_name: &str,
+) -> Vec<(String, String)> {
+    let old_base = unscoped_name(old_name);
+    let new_base = unscoped_name(new_name);
+    let mut renames = Vec::new();
+
+    document["name"] = serde_json::json!(new_name);
+    if document.get("_id").is_some() {
+        document["_id"] = serde_json::json!(new_name);
+    }
+
+    if let Some(versions) = document.get_mut("versions").and_then(|v| v.as_object_mut()) {
+        for (version, info) in versions.iter_mut() {
+            let old_file = tarball_filename(old_name, version, info);
+            let new_file = old_file.replacen(old_base, new_base, 1);
+
+            info["name"] = serde_json::json!(new_name);
+            info["_id"] = serde_json::json!(format!("{}@{}", new_name, version));
+            if let Some(tarball) = info
+                .get("dist")
+                .and_then(|d| d.get("tarball"))
+                .and_then(|t| t.as_str())
+            {
+                let new_url = tarball.replace(
+                    &format!("/{}/-/{}", old_name, old_file),
+                    &format!("/{}/-/{}", new_name, new_file),
+                );
+                info["dist"]["tarball"] = serde_json::json!(new_url);
+            }
+
+            renames.push((old_file, new_file));
+        }
+    }
+
+    if let Some(attachments) = document.get_mut("_attachments").and_then(|a| a.as_object_mut()) {
+        for (old_file, new_file) in &renames {
+            if let Some(attachment) = attachments.remove(old_file) {
+                attachments.insert(new_file.clone(), attachment);
+            }
+        }
+    }
+
+    renames
+}
+
+/// 写入包文档
+fn write_package_document(package_path: &Path, document: &serde_json::Value) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(document)
+        .map_err(|e| format!("序列化 package.json 失败: {}", e))?;
+    atomic_write(&package_path.join("package.json"), content)
+        .map_err(|e| format!("写入 package.json 失败: {}", e))
+}
+
+/// 计算目录占用的磁盘空间（字节）
+pub(crate) fn dir_size(path: &Path) -> u64 {
+    std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| match entry.metadata() {
+                    Ok(m) if m.is_dir() => dir_size(&entry.path()),
+                    Ok(m) => m.len(),
+                    Err(_) => 0,
+                })
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// 转义 CSV 字段
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// 将包清单序列化为 CSV
+fn inventory_to_csv(entries: &[InventoryEntry]) -> String {
+    let mut csv = String::from("name,latest_version,version_count,size_bytes,origin,modified\n");
+    for entry in entries {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            csv_field(&entry.name),
+            csv_field(&entry.latest_version),
+            entry.version_count,
+            entry.size_bytes,
+            entry.origin,
+            csv_field(entry.modified.as_deref().unwrap_or_default()),
+        ));
+    }
+    csv
+}
+
 // ============= Tauri 命令 =============
 
 /// 获取包列表（分页）
+///
+/// `include_hidden` 为 true 时包含以 `.` 开头的隐藏条目（默认不包含），
+/// 隐藏条目不在私有包列表中，按类型筛选时归入缓存包
 #[tauri::command]
 pub async fn get_packages(
+    cache: State<'_, PrivatePackageCache>,
     port: u16,
     package_type: PackageType,
     page: usize,
     page_size: usize,
+    include_hidden: Option<bool>,
 ) -> Result<PaginatedResult<PackageInfo>, String> {
-    let storage_path = get_storage_path();
-    let all_dirs = collect_package_dirs(&storage_path)?;
+    let storage_path = get_storage_path()?;
+    let all_dirs = collect_package_dirs(&storage_path, include_hidden.unwrap_or(false))?;
 
     // 获取所有包名
     let all_names: Vec<String> = all_dirs.iter().map(|(_, name)| name.clone()).collect();
 
     // 根据类型过滤
-    let filtered_names = filter_package_names_by_type(all_names, package_type, port).await?;
+    let filtered_names = filter_package_names_by_type(&cache, all_names, package_type, port).await?;
 
     let total = filtered_names.len();
     let total_pages = if total == 0 {
@@ -354,9 +1142,9 @@ pub async fn get_packages(
     let items: Vec<PackageInfo> = page_names
         .into_iter()
         .filter_map(|name| {
-            name_to_path
-                .get(&name)
-                .and_then(|path| read_package_info(path, &name))
+            let path = name_to_path.get(&name)?;
+            read_package_info(path, &name)
+                .or_else(|| name.starts_with('.').then(|| hidden_entry_info(path, &name)))
         })
         .collect();
 
@@ -369,54 +1157,1291 @@ pub async fn get_packages(
     })
 }
 
-/// 获取包数量
+/// 获取包数量（`include_hidden` 与 [`get_packages`] 含义相同，保证数量与列表总数一致）
 #[tauri::command]
-pub async fn get_package_count(port: u16, package_type: PackageType) -> Result<usize, String> {
-    let storage_path = get_storage_path();
-    let all_dirs = collect_package_dirs(&storage_path)?;
+pub async fn get_package_count(
+    cache: State<'_, PrivatePackageCache>,
+    port: u16,
+    package_type: PackageType,
+    include_hidden: Option<bool>,
+) -> Result<usize, String> {
+    let storage_path = get_storage_path()?;
+    let all_dirs = collect_package_dirs(&storage_path, include_hidden.unwrap_or(false))?;
 
     let all_names: Vec<String> = all_dirs.into_iter().map(|(_, name)| name).collect();
-    let filtered_names = filter_package_names_by_type(all_names, package_type, port).await?;
+    let filtered_names = filter_package_names_by_type(&cache, all_names, package_type, port).await?;
 
     Ok(filtered_names.len())
 }
 
-/// 删除包
+/// 一次性获取私有、缓存及全部包的数量（只扫描一次目录、只请求一次 API）
+///
+/// `include_hidden` 与 [`get_packages`] 含义相同，隐藏条目计入缓存包
 #[tauri::command]
-pub async fn delete_package(package_name: String) -> Result<(), String> {
-    let storage_path = get_storage_path();
-    let package_path = get_package_path(&storage_path, &package_name);
+pub async fn get_package_counts(
+    cache: State<'_, PrivatePackageCache>,
+    port: u16,
+    include_hidden: Option<bool>,
+) -> Result<PackageCounts, String> {
+    let storage_path = get_storage_path()?;
+    let all_dirs = collect_package_dirs(&storage_path, include_hidden.unwrap_or(false))?;
+    let private_names = get_private_package_names(&cache, port).await?;
 
-    if !package_path.exists() {
-        return Err("包不存在".to_string());
-    }
+    let all = all_dirs.len();
+    let private = all_dirs
+        .iter()
+        .filter(|(_, name)| private_names.contains(name))
+        .count();
 
-    std::fs::remove_dir_all(&package_path).map_err(|e| format!("删除包失败: {}", e))
+    Ok(PackageCounts {
+        private,
+        cached: all - private,
+        all,
+    })
+}
+
+/// 获取仓库统计信息（只扫描一次目录、只请求一次 API）
+#[tauri::command]
+pub async fn get_registry_stats(cache: State<'_, PrivatePackageCache>, port: u16) -> Result<RegistryStats, String> {
+    let storage_path = get_storage_path()?;
+    let all_dirs = collect_package_dirs(&storage_path, false)?;
+
+    let mut total_versions = 0;
+    let mut storage_bytes = 0;
+    for (path, _) in &all_dirs {
+        total_versions += read_package_document(path)
+            .ok()
+            .and_then(|doc| doc.get("versions").and_then(|v| v.as_object()).map(|v| v.len()))
+            .unwrap_or(0);
+        storage_bytes += dir_size(path);
+    }
+
+    let private = match get_private_package_names(&cache, port).await {
+        Ok(private_names) => Some(
+            all_dirs
+                .iter()
+                .filter(|(_, name)| private_names.contains(name))
+                .count(),
+        ),
+        Err(e) if e.starts_with(API_UNAVAILABLE_ERROR) => None,
+        Err(e) => return Err(e),
+    };
+
+    let user_count = get_users().await?.len();
+
+    Ok(RegistryStats {
+        total_packages: all_dirs.len(),
+        private,
+        cached: private.map(|p| all_dirs.len() - p),
+        total_versions,
+        storage_bytes,
+        user_count,
+    })
+}
+
+/// 校验包各版本 tarball 的完整性（shasum / integrity）
+#[tauri::command]
+pub async fn verify_package_integrity(package_name: String) -> Result<Vec<TarballIntegrity>, String> {
+    validate_package_name(&package_name)?;
+
+    let storage_path = get_storage_path()?;
+    let package_path = get_package_path(&storage_path, &package_name);
+
+    if !is_valid_package_dir(&package_path) {
+        return Err("包不存在".to_string());
+    }
+
+    let document = read_package_document(&package_path)?;
+    let Some(versions) = document.get("versions").and_then(|v| v.as_object()) else {
+        return Ok(vec![]);
+    };
+
+    let mut report: Vec<TarballIntegrity> = versions
+        .iter()
+        .map(|(version, info)| verify_tarball(&package_path, &package_name, version, info))
+        .collect();
+    report.sort_by(|a, b| version_compare(&b.version, &a.version));
+
+    Ok(report)
+}
+
+/// 根据本地 tarball 计算 npm 使用的 `integrity`（`sha512-...`），返回计算结果
+///
+/// `write_back` 为 true 且文档中缺少 `dist.integrity` 时写回包文档；文档中有 `shasum`
+/// 且与 tarball 不一致时拒绝写回，避免为损坏的 tarball 生成完整性信息
+#[tauri::command]
+pub async fn compute_integrity(
+    package_name: String,
+    version: String,
+    write_back: bool,
+) -> Result<String, String> {
+    validate_package_name(&package_name)?;
+
+    let storage_path = get_storage_path()?;
+    let package_path = get_package_path(&storage_path, &package_name);
+
+    if !is_valid_package_dir(&package_path) {
+        return Err("包不存在".to_string());
+    }
+
+    let mut document = read_package_document(&package_path)?;
+    let version_info = document
+        .get("versions")
+        .and_then(|v| v.get(&version))
+        .ok_or_else(|| format!("版本 {} 不存在", version))?;
+
+    let filename = tarball_filename(&package_name, &version, version_info);
+    let data = std::fs::read(package_path.join(&filename))
+        .map_err(|e| format!("读取 tarball {} 失败: {}", filename, e))?;
+    let integrity = sha512_integrity(&data);
+
+    let dist = version_info.get("dist");
+    let has_integrity = dist.and_then(|d| d.get("integrity")).is_some();
+    if !write_back || has_integrity {
+        return Ok(integrity);
+    }
+
+    if let Some(shasum) = dist.and_then(|d| d.get("shasum")).and_then(|v| v.as_str()) {
+        if shasum != sha1_hex(&data) {
+            return Err(format!("tarball {} 与文档中的 shasum 不一致，拒绝写入 integrity", filename));
+        }
+    }
+
+    document["versions"][&version]["dist"]["integrity"] = serde_json::json!(integrity);
+    write_package_document(&package_path, &document)?;
+
+    Ok(integrity)
+}
+
+/// 保留原有 dist-tags 中目标版本仍存在的标签，`latest` 缺失或失效时改为指向 `fallback_latest`
+fn rebuild_dist_tags(
+    existing: Option<&serde_json::Value>,
+    versions: &serde_json::Map<String, serde_json::Value>,
+    fallback_latest: &str,
+) -> serde_json::Value {
+    let mut tags: serde_json::Map<String, serde_json::Value> = existing
+        .and_then(|tags| tags.as_object())
+        .map(|tags| {
+            tags.iter()
+                .filter(|(_, version)| version.as_str().is_some_and(|v| versions.contains_key(v)))
+                .map(|(tag, version)| (tag.clone(), version.clone()))
+                .collect()
+        })
+        .unwrap_or_default();
+    tags.entry("latest").or_insert_with(|| serde_json::json!(fallback_latest));
+    serde_json::Value::Object(tags)
+}
+
+/// 扫描包目录中的 tarball，根据其内部的 package.json 重建包文档的 `versions`、`time` 与 `dist-tags`
+///
+/// 用于恢复包文档损坏但 tarball 完好的包。原文档存在时先备份为 `package.json.{时间戳}.bak`；
+/// 原文档仍可解析时保留其余字段（如 `_uplinks`、`readme`），否则重新生成。
+/// 发布时间取自 tarball 文件的修改时间。原有 dist-tags 中指向的版本仍存在时保留该标签，
+/// `latest` 缺失或失效时指向最高的正式版本（没有正式版本时取最高版本）
+#[tauri::command]
+pub async fn rebuild_package_metadata(
+    cache: State<'_, PrivatePackageCache>,
+    package_name: String,
+) -> Result<RebuiltMetadata, String> {
+    validate_package_name(&package_name)?;
+
+    let storage_path = get_storage_path()?;
+    let package_path = get_package_path(&storage_path, &package_name);
+    if !package_path.is_dir() {
+        return Err("包不存在".to_string());
+    }
+
+    let entries = std::fs::read_dir(&package_path).map_err(|e| format!("读取包目录失败: {}", e))?;
+    let mut tarballs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|e| e == "tgz"))
+        .collect();
+    tarballs.sort();
+
+    let port = load_app_settings()?.default_port;
+    let mut versions = serde_json::Map::new();
+    let mut times: Vec<(String, String)> = Vec::new();
+    let mut attachments = serde_json::Map::new();
+    let mut skipped = Vec::new();
+
+    for tarball_path in tarballs {
+        let filename = tarball_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let Ok(data) = std::fs::read(&tarball_path) else {
+            skipped.push(filename);
+            continue;
+        };
+        let Ok(mut manifest) = read_tarball_manifest(&data) else {
+            skipped.push(filename);
+            continue;
+        };
+
+        let name = manifest.get("name").and_then(|v| v.as_str());
+        let version = manifest
+            .get("version")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+        let Some(version) = version.filter(|_| name == Some(package_name.as_str())) else {
+            skipped.push(filename);
+            continue;
+        };
+
+        let shasum = sha1_hex(&data);
+        manifest["_id"] = serde_json::json!(format!("{}@{}", package_name, version));
+        manifest["dist"] = serde_json::json!({
+            "shasum": shasum,
+            "integrity": sha512_integrity(&data),
+            "tarball": format!("http://localhost:{}/{}/-/{}", port, package_name, filename),
+        });
+
+        let published: chrono::DateTime<chrono::Utc> = std::fs::metadata(&tarball_path)
+            .and_then(|m| m.modified())
+            .map(Into::into)
+            .unwrap_or_else(|_| chrono::Utc::now());
+        times.push((
+            version.clone(),
+            published.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        ));
+        attachments.insert(filename, serde_json::json!({ "shasum": shasum, "version": version }));
+        versions.insert(version, manifest);
+    }
+
+    if versions.is_empty() {
+        return Err("包目录中没有可用的 tarball，无法重建包文档".to_string());
+    }
+
+    let mut version_names: Vec<String> = versions.keys().cloned().collect();
+    version_names.sort_by(|a, b| version_compare(b, a));
+    let latest = version_names
+        .iter()
+        .find(|v| semver::Version::parse(v).is_ok_and(|v| v.pre.is_empty()))
+        .unwrap_or(&version_names[0])
+        .clone();
+
+    let mut time = serde_json::Map::new();
+    let created = times.iter().map(|(_, t)| t).min().cloned();
+    let modified = times.iter().map(|(_, t)| t).max().cloned();
+    time.insert("created".to_string(), serde_json::json!(created));
+    time.insert("modified".to_string(), serde_json::json!(modified));
+    for (version, published) in times {
+        time.insert(version, serde_json::json!(published));
+    }
+
+    let document_path = package_path.join("package.json");
+    let mut document = read_package_document(&package_path)
+        .ok()
+        .filter(|d| d.is_object())
+        .unwrap_or_else(|| {
+            serde_json::json!({
+                "name": package_name,
+                "_uplinks": {},
+                "_distfiles": {},
+                "_rev": "",
+            })
+        });
+
+    if document_path.exists() {
+        let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+        std::fs::copy(&document_path, package_path.join(format!("package.json.{}.bak", timestamp)))
+            .map_err(|e| format!("备份 package.json 失败: {}", e))?;
+    }
+
+    document["name"] = serde_json::json!(package_name);
+    document["dist-tags"] = rebuild_dist_tags(document.get("dist-tags"), &versions, &latest);
+    document["versions"] = serde_json::Value::Object(versions);
+    document["time"] = serde_json::Value::Object(time);
+    document["_attachments"] = serde_json::Value::Object(attachments);
+
+    write_package_document(&package_path, &document)?;
+    cache.invalidate();
+
+    Ok(RebuiltMetadata {
+        versions: version_names,
+        skipped,
+    })
+}
+
+/// 将本地 npm tarball 导入到存储目录
+///
+/// 根据 tarball 内的 package.json 确定包名与版本，将 tarball 放入对应的包目录并更新包文档。
+/// 运行中的 Verdaccio 可能缓存了旧的包文档，导入后需要重启服务才能看到新版本
+#[tauri::command]
+pub async fn import_tarball(
+    cache: State<'_, PrivatePackageCache>,
+    path: String,
+) -> Result<ImportedTarball, String> {
+    let data = std::fs::read(&path).map_err(|e| format!("读取 tarball 失败: {}", e))?;
+    let mut manifest = read_tarball_manifest(&data)?;
+
+    let name = manifest
+        .get("name")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| "package.json 缺少 name 字段".to_string())?
+        .to_string();
+    let version = manifest
+        .get("version")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| "package.json 缺少 version 字段".to_string())?
+        .to_string();
+
+    validate_package_name(&name)?;
+    // 版本号会拼入 tarball 文件名，必须是合法的 semver，避免 `1/../../x` 之类的值写出存储目录
+    semver::Version::parse(&version).map_err(|e| format!("无效的版本号 {}: {}", version, e))?;
+
+    let storage_path = get_storage_path()?;
+    let package_path = get_package_path(&storage_path, &name);
+
+    let mut document = if package_path.join("package.json").exists() {
+        read_package_document(&package_path)?
+    } else {
+        serde_json::json!({
+            "name": name,
+            "versions": {},
+            "time": {},
+            "_uplinks": {},
+            "_distfiles": {},
+            "_attachments": {},
+            "_rev": "",
+            "dist-tags": {},
+        })
+    };
+
+    if document
+        .get("versions")
+        .and_then(|v| v.get(&version))
+        .is_some()
+    {
+        return Err(format!("版本 {}@{} 已存在", name, version));
+    }
+
+    let filename = format!("{}-{}.tgz", unscoped_name(&name), version);
+    let port = load_app_settings()?.default_port;
+    let shasum = sha1_hex(&data);
+
+    manifest["_id"] = serde_json::json!(format!("{}@{}", name, version));
+    manifest["dist"] = serde_json::json!({
+        "shasum": shasum,
+        "integrity": sha512_integrity(&data),
+        "tarball": format!("http://localhost:{}/{}/-/{}", port, name, filename),
+    });
+
+    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+    let should_tag_latest = document
+        .get("dist-tags")
+        .and_then(|t| t.get("latest"))
+        .and_then(|v| v.as_str())
+        .map(|latest| version_compare(&version, latest) == std::cmp::Ordering::Greater)
+        .unwrap_or(true);
+
+    let doc = document
+        .as_object_mut()
+        .ok_or_else(|| "package.json 格式无效".to_string())?;
+    for key in ["versions", "time", "_attachments", "dist-tags"] {
+        if !doc.get(key).is_some_and(|v| v.is_object()) {
+            doc.insert(key.to_string(), serde_json::json!({}));
+        }
+    }
+    doc["versions"][&version] = manifest;
+    doc["_attachments"][&filename] = serde_json::json!({ "shasum": shasum, "version": version });
+    if doc["time"].get("created").is_none() {
+        doc["time"]["created"] = serde_json::json!(now);
+    }
+    doc["time"]["modified"] = serde_json::json!(now);
+    doc["time"][&version] = serde_json::json!(now);
+    if should_tag_latest {
+        doc["dist-tags"]["latest"] = serde_json::json!(version);
+    }
+
+    std::fs::create_dir_all(&package_path).map_err(|e| format!("创建包目录失败: {}", e))?;
+    atomic_write(&package_path.join(&filename), &data)
+        .map_err(|e| format!("写入 tarball 失败: {}", e))?;
+
+    write_package_document(&package_path, &document)?;
+    cache.invalidate();
+
+    Ok(ImportedTarball { name, version })
+}
+
+/// 获取包各版本的 tarball 文件名、本地大小、shasum 与下载地址（版本从新到旧）
+#[tauri::command]
+pub async fn get_package_tarballs(port: u16, package_name: String) -> Result<Vec<TarballEntry>, String> {
+    validate_package_name(&package_name)?;
+
+    let storage_path = get_storage_path()?;
+    let package_path = get_package_path(&storage_path, &package_name);
+
+    if !is_valid_package_dir(&package_path) {
+        return Err("包不存在".to_string());
+    }
+
+    let document = read_package_document(&package_path)?;
+    let Some(versions) = document.get("versions").and_then(|v| v.as_object()) else {
+        return Ok(vec![]);
+    };
+
+    let mut entries: Vec<TarballEntry> = versions
+        .iter()
+        .map(|(version, info)| {
+            let tarball_filename = tarball_filename(&package_name, version, info);
+            TarballEntry {
+                size_bytes: std::fs::metadata(package_path.join(&tarball_filename))
+                    .ok()
+                    .map(|m| m.len()),
+                shasum: info
+                    .get("dist")
+                    .and_then(|d| d.get("shasum"))
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string()),
+                url: format!("http://localhost:{}/{}/-/{}", port, package_name, tarball_filename),
+                version: version.clone(),
+                tarball_filename,
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| version_compare(&b.version, &a.version));
+
+    Ok(entries)
+}
+
+/// 获取包文档 `time` 中记录的发布历史（含 created / modified），按时间从早到晚排序
+///
+/// 只读取存储中的包文档；没有 `time` 字段时返回空列表，无法解析的时间排在最后
+#[tauri::command]
+pub async fn get_package_publish_history(package_name: String) -> Result<Vec<PublishHistoryEntry>, String> {
+    validate_package_name(&package_name)?;
+
+    let storage_path = get_storage_path()?;
+    let package_path = get_package_path(&storage_path, &package_name);
+
+    if !is_valid_package_dir(&package_path) {
+        return Err("包不存在".to_string());
+    }
+
+    let document = read_package_document(&package_path)?;
+    let Some(time) = document.get("time").and_then(|t| t.as_object()) else {
+        return Ok(vec![]);
+    };
+
+    let mut entries: Vec<PublishHistoryEntry> = time
+        .iter()
+        .filter_map(|(key, value)| {
+            Some(PublishHistoryEntry {
+                key: key.clone(),
+                timestamp: value.as_str()?.to_string(),
+            })
+        })
+        .collect();
+    entries.sort_by_cached_key(|entry| {
+        let parsed = chrono::DateTime::parse_from_rfc3339(&entry.timestamp).ok();
+        (parsed.is_none(), parsed, entry.timestamp.clone())
+    });
+
+    Ok(entries)
+}
+
+/// 获取包指定版本的依赖（未指定版本时使用 latest），并标记哪些依赖在本地存储中可用
+#[tauri::command]
+pub async fn get_package_dependencies(
+    package_name: String,
+    version: Option<String>,
+) -> Result<PackageDependencies, String> {
+    validate_package_name(&package_name)?;
+
+    let storage_path = get_storage_path()?;
+    let package_path = get_package_path(&storage_path, &package_name);
+
+    if !is_valid_package_dir(&package_path) {
+        return Err("包不存在".to_string());
+    }
+
+    let document = read_package_document(&package_path)?;
+    let version = match version {
+        Some(v) => v,
+        None => document
+            .get("dist-tags")
+            .and_then(|t| t.get("latest"))
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| "包没有 latest 版本".to_string())?
+            .to_string(),
+    };
+
+    let version_info = document
+        .get("versions")
+        .and_then(|v| v.get(&version))
+        .ok_or_else(|| format!("版本 {} 不存在", version))?;
+
+    let local_names: std::collections::HashSet<String> = collect_package_dirs(&storage_path, false)?
+        .into_iter()
+        .map(|(_, name)| name)
+        .collect();
+
+    Ok(PackageDependencies {
+        dependencies: parse_dependency_map(version_info, "dependencies", &local_names),
+        dev_dependencies: parse_dependency_map(version_info, "devDependencies", &local_names),
+        peer_dependencies: parse_dependency_map(version_info, "peerDependencies", &local_names),
+        version,
+    })
+}
+
+/// 查找依赖指定包的所有包（扫描 dependencies / devDependencies / peerDependencies / optionalDependencies）
+///
+/// `all_versions` 为 false 时只检查各包的 latest 版本；`version_range` 按依赖声明的基础版本过滤
+#[tauri::command]
+pub async fn find_packages_depending_on(
+    dep_name: String,
+    version_range: Option<String>,
+    all_versions: bool,
+) -> Result<Vec<DependentEntry>, String> {
+    let ranges = version_range
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+        .map(parse_npm_range)
+        .transpose()?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let all_dirs = collect_package_dirs(&get_storage_path()?, false)?;
+        let mut results = Vec::new();
+
+        for (path, name) in all_dirs {
+            let Ok(document) = read_package_document(&path) else {
+                continue;
+            };
+            let Some(versions) = document.get("versions").and_then(|v| v.as_object()) else {
+                continue;
+            };
+
+            let selected: Vec<String> = if all_versions {
+                versions.keys().cloned().collect()
+            } else {
+                latest_version(&document).into_iter().collect()
+            };
+
+            for version in selected {
+                let Some(version_info) = versions.get(&version) else {
+                    continue;
+                };
+                let dep_version = ["dependencies", "devDependencies", "peerDependencies", "optionalDependencies"]
+                    .iter()
+                    .find_map(|key| version_info.get(key).and_then(|d| d.get(&dep_name)))
+                    .and_then(|v| v.as_str());
+                let Some(dep_version) = dep_version else {
+                    continue;
+                };
+                if ranges.as_ref().is_some_and(|r| !dependency_matches_range(dep_version, r)) {
+                    continue;
+                }
+                results.push(DependentEntry {
+                    package: name.clone(),
+                    version,
+                    dep_version: dep_version.to_string(),
+                });
+            }
+        }
+
+        results.sort_by(|a, b| a.package.cmp(&b.package).then_with(|| version_compare(&a.version, &b.version)));
+        Ok(results)
+    })
+    .await
+    .map_err(|e| format!("查找依赖失败: {}", e))?
+}
+
+/// 查找每个包中超出最新 `keep_latest` 个版本的旧版本（不会删除任何文件）
+///
+/// 默认跳过私有包，`include_private` 为 true 时才包含；被 dist-tags 引用的版本始终保留
+#[tauri::command]
+pub async fn find_prunable_versions(keep_latest: usize, include_private: bool) -> Result<PrunePlan, String> {
+    build_prune_plan(keep_latest, include_private)
+}
+
+/// 删除每个包中超出最新 `keep_latest` 个版本的旧版本，返回实际清理的结果
+///
+/// 单个版本或包删除失败时记录到 `failed` 并继续处理其余版本
+#[tauri::command]
+pub async fn prune_old_versions(keep_latest: usize, include_private: bool) -> Result<PruneResult, String> {
+    let plan = build_prune_plan(keep_latest, include_private)?;
+    let storage_path = get_storage_path()?;
+
+    let mut packages = Vec::new();
+    let mut failed = Vec::new();
+    for entry in plan.packages {
+        let package_path = get_package_path(&storage_path, &entry.package);
+        let mut document = match read_package_document(&package_path) {
+            Ok(document) => document,
+            Err(error) => {
+                failed.push(DeleteFailure { name: entry.package, error });
+                continue;
+            }
+        };
+        // 生成计划后可能又有本地发布的版本，再次确认仍是缓存包
+        if !include_private && !is_uplink_cache_document(&entry.package, &document) {
+            continue;
+        }
+
+        let mut versions = Vec::new();
+        let mut reclaimable_bytes = 0;
+        for version in entry.versions {
+            match remove_package_version(&package_path, &entry.package, &mut document, &version) {
+                Ok(size) => {
+                    reclaimable_bytes += size;
+                    versions.push(version);
+                }
+                Err(error) => failed.push(DeleteFailure {
+                    name: format!("{}@{}", entry.package, version),
+                    error,
+                }),
+            }
+        }
+
+        if versions.is_empty() {
+            continue;
+        }
+        // 已移除的版本必须写回文档，避免文档引用已删除的 tarball
+        if let Err(error) = write_package_document(&package_path, &document) {
+            failed.push(DeleteFailure { name: entry.package, error });
+            continue;
+        }
+        packages.push(PrunablePackage {
+            package: entry.package,
+            versions,
+            reclaimable_bytes,
+        });
+    }
+
+    let total_bytes = packages.iter().map(|p| p.reclaimable_bytes).sum();
+    Ok(PruneResult {
+        packages,
+        total_bytes,
+        failed,
+    })
+}
+
+/// 以新名称复制一个包（复制目录并改写包文档中的名称与 tarball 文件名）
+///
+/// tarball 内部的 package.json 不会改写。运行中的 Verdaccio 需要重启后才能识别新包
+#[tauri::command]
+pub async fn clone_package(
+    cache: State<'_, PrivatePackageCache>,
+    source_name: String,
+    target_name: String,
+) -> Result<(), String> {
+    validate_package_name(&source_name)?;
+    validate_package_name(&target_name)?;
+
+    let storage_path = get_storage_path()?;
+    let source_path = get_package_path(&storage_path, &source_name);
+    let target_path = get_package_path(&storage_path, &target_name);
+
+    if !is_valid_package_dir(&source_path) {
+        return Err("包不存在".to_string());
+    }
+    if target_path.exists() {
+        return Err(format!("包 {} 已存在", target_name));
+    }
+
+    let mut document = read_package_document(&source_path)?;
+    let renames: std::collections::HashMap<String, String> =
+        rename_package_document(&mut document, &source_name, &target_name)
+            .into_iter()
+            .collect();
+
+    std::fs::create_dir_all(&target_path).map_err(|e| format!("创建包目录失败: {}", e))?;
+
+    let entries = std::fs::read_dir(&source_path).map_err(|e| format!("读取包目录失败: {}", e))?;
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if file_name == "package.json" || !entry.path().is_file() {
+            continue;
+        }
+        let target_file = renames.get(&file_name).unwrap_or(&file_name);
+        std::fs::copy(entry.path(), target_path.join(target_file))
+            .map_err(|e| format!("复制 {} 失败: {}", file_name, e))?;
+    }
+
+    write_package_document(&target_path, &document)?;
+    cache.invalidate();
+
+    Ok(())
+}
+
+/// 重命名包（移动存储目录并改写包文档中的所有名称引用）
+///
+/// 支持 scope 变更（如 `pkg` → `@scope/pkg`）。服务运行时拒绝执行，避免对外提供改了一半的数据
+#[tauri::command]
+pub async fn rename_package(
+    process: State<'_, VerdaccioProcess>,
+    cache: State<'_, PrivatePackageCache>,
+    old_name: String,
+    new_name: String,
+) -> Result<(), String> {
+    if process.check_running() {
+        return Err("请先停止 Verdaccio 再重命名包".to_string());
+    }
+
+    validate_package_name(&old_name)?;
+    validate_package_name(&new_name)?;
+
+    let storage_path = get_storage_path()?;
+    let old_path = get_package_path(&storage_path, &old_name);
+    let new_path = get_package_path(&storage_path, &new_name);
+
+    if !is_valid_package_dir(&old_path) {
+        return Err("包不存在".to_string());
+    }
+    if new_path.exists() {
+        return Err(format!("包 {} 已存在", new_name));
+    }
+
+    let mut document = read_package_document(&old_path)?;
+    let renames = rename_package_document(&mut document, &old_name, &new_name);
+
+    if let Some(parent) = new_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+    }
+    std::fs::rename(&old_path, &new_path).map_err(|e| format!("移动包目录失败: {}", e))?;
+
+    for (old_file, new_file) in renames {
+        let old_file_path = new_path.join(&old_file);
+        if old_file != new_file && old_file_path.exists() {
+            std::fs::rename(&old_file_path, new_path.join(&new_file))
+                .map_err(|e| format!("重命名 {} 失败: {}", old_file, e))?;
+        }
+    }
+
+    write_package_document(&new_path, &document)?;
+    cache.invalidate();
+
+    // 移除已经空了的原 scope 目录
+    if old_name.starts_with('@') {
+        if let Some(scope_dir) = old_path.parent() {
+            let _ = std::fs::remove_dir(scope_dir);
+        }
+    }
+
+    Ok(())
+}
+
+/// 导出全部包的清单（JSON 或 CSV），返回导出的包数量
+///
+/// 在阻塞线程中读取所有包文档，并通过 `inventory-export-progress` 事件报告进度
+#[tauri::command]
+pub async fn export_package_inventory(
+    app: tauri::AppHandle,
+    cache: State<'_, PrivatePackageCache>,
+    port: u16,
+    format: ExportFormat,
+    path: String,
+) -> Result<usize, String> {
+    let storage_path = get_storage_path()?;
+    let all_dirs = collect_package_dirs(&storage_path, false)?;
+    let private_names = get_private_package_names(&cache, port).await?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        use tauri::Emitter;
+
+        let total = all_dirs.len();
+        let mut entries = Vec::with_capacity(total);
+
+        for (index, (package_path, name)) in all_dirs.iter().enumerate() {
+            if let Some(info) = read_package_info(package_path, name) {
+                let origin = if private_names.contains(name) { "private" } else { "cached" };
+                entries.push(InventoryEntry {
+                    origin: origin.to_string(),
+                    latest_version: info.version,
+                    version_count: info.versions.len(),
+                    size_bytes: dir_size(package_path),
+                    modified: info.modified,
+                    name: info.name,
+                });
+            }
+
+            if (index + 1) % 50 == 0 || index + 1 == total {
+                let _ = app.emit(
+                    "inventory-export-progress",
+                    ExportProgress {
+                        current: index + 1,
+                        total,
+                    },
+                );
+            }
+        }
+
+        let content = match format {
+            ExportFormat::Json => serde_json::to_string_pretty(&entries)
+                .map_err(|e| format!("序列化包清单失败: {}", e))?,
+            ExportFormat::Csv => inventory_to_csv(&entries),
+        };
+        std::fs::write(&path, content).map_err(|e| format!("写入导出文件失败: {}", e))?;
+
+        Ok(entries.len())
+    })
+    .await
+    .map_err(|e| format!("导出任务失败: {}", e))?
+}
+
+/// 删除包
+#[tauri::command]
+pub async fn delete_package(cache: State<'_, PrivatePackageCache>, package_name: String) -> Result<(), String> {
+    validate_package_name(&package_name)?;
+
+    let storage_path = get_storage_path()?;
+    let package_path = get_package_path(&storage_path, &package_name);
+
+    if !package_path.exists() {
+        return Err("包不存在".to_string());
+    }
+
+    remove_package_dir(&storage_path, &package_path)?;
+    cache.invalidate();
+
+    Ok(())
+}
+
+/// 将包目录打包为 zip（条目路径为 `{包名}/{文件名}`，便于还原到原位置）
+fn write_package_archive(package_path: &Path, package_name: &str, archive_path: &Path) -> Result<(), String> {
+    use std::io::Write;
+
+    let file = std::fs::File::create(archive_path).map_err(|e| format!("创建归档文件失败: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    let entries = std::fs::read_dir(package_path).map_err(|e| format!("读取包目录失败: {}", e))?;
+    for entry in entries.flatten() {
+        if !entry.path().is_file() {
+            continue;
+        }
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let data = std::fs::read(entry.path()).map_err(|e| format!("读取 {} 失败: {}", file_name, e))?;
+        zip.start_file(format!("{}/{}", package_name, file_name), options)
+            .map_err(|e| format!("写入归档失败: {}", e))?;
+        zip.write_all(&data).map_err(|e| format!("写入归档失败: {}", e))?;
+    }
+
+    zip.finish().map_err(|e| format!("写入归档失败: {}", e))?;
+    Ok(())
+}
+
+/// 归档包后删除（可通过 `restore_package_archive` 还原）
+///
+/// 归档文件为 `{dest_dir}/{包名}-{时间戳}.zip`，scoped 包名中的 `/` 替换为 `__`，返回归档文件路径
+#[tauri::command]
+pub async fn archive_package(
+    cache: State<'_, PrivatePackageCache>,
+    package_name: String,
+    dest_dir: String,
+) -> Result<String, String> {
+    validate_package_name(&package_name)?;
+
+    let storage_path = get_storage_path()?;
+    let package_path = get_package_path(&storage_path, &package_name);
+    if !is_valid_package_dir(&package_path) {
+        return Err("包不存在".to_string());
+    }
+
+    let dest_dir = PathBuf::from(dest_dir);
+    std::fs::create_dir_all(&dest_dir).map_err(|e| format!("创建归档目录失败: {}", e))?;
+
+    let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let archive_path = dest_dir.join(format!(
+        "{}-{}.zip",
+        package_name.replace('/', "__"),
+        timestamp
+    ));
+
+    if let Err(e) = write_package_archive(&package_path, &package_name, &archive_path) {
+        let _ = std::fs::remove_file(&archive_path);
+        return Err(e);
+    }
+
+    remove_package_dir(&storage_path, &package_path)?;
+    cache.invalidate();
+
+    Ok(archive_path.to_string_lossy().to_string())
+}
+
+/// 从 `archive_package` 生成的归档还原包，返回还原的包名
+#[tauri::command]
+pub async fn restore_package_archive(
+    cache: State<'_, PrivatePackageCache>,
+    zip_path: String,
+) -> Result<String, String> {
+    let file = std::fs::File::open(&zip_path).map_err(|e| format!("打开归档文件失败: {}", e))?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| format!("读取归档文件失败: {}", e))?;
+
+    // 先检查所有条目都位于同一个包目录下，且包含 package.json
+    let mut package_name: Option<String> = None;
+    let mut has_package_json = false;
+    for i in 0..archive.len() {
+        let entry = archive.by_index(i).map_err(|e| format!("读取归档文件失败: {}", e))?;
+        if entry.is_dir() {
+            continue;
+        }
+        let path = entry
+            .enclosed_name()
+            .ok_or_else(|| format!("归档中包含非法路径: {}", entry.name()))?;
+        let parent = path
+            .parent()
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .filter(|p| !p.is_empty())
+            .ok_or_else(|| format!("归档中包含非法路径: {}", entry.name()))?;
+
+        match &package_name {
+            Some(name) if *name != parent => return Err("归档中包含多个包".to_string()),
+            Some(_) => {}
+            None => package_name = Some(parent),
+        }
+        if path.file_name().is_some_and(|n| n == "package.json") {
+            has_package_json = true;
+        }
+    }
+
+    let package_name = package_name.ok_or_else(|| "归档文件为空".to_string())?;
+    if !has_package_json {
+        return Err("归档中缺少 package.json".to_string());
+    }
+    validate_package_name(&package_name)?;
+
+    let storage_path = get_storage_path()?;
+    let package_path = get_package_path(&storage_path, &package_name);
+    if package_path.exists() {
+        return Err(format!("包 {} 已存在", package_name));
+    }
+
+    std::fs::create_dir_all(&package_path).map_err(|e| format!("创建包目录失败: {}", e))?;
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| format!("读取归档文件失败: {}", e))?;
+        if entry.is_dir() {
+            continue;
+        }
+        let Some(file_name) = entry.enclosed_name().and_then(|p| p.file_name().map(|n| n.to_os_string())) else {
+            continue;
+        };
+        let mut output = std::fs::File::create(package_path.join(&file_name))
+            .map_err(|e| format!("还原 {} 失败: {}", file_name.to_string_lossy(), e))?;
+        std::io::copy(&mut entry, &mut output)
+            .map_err(|e| format!("还原 {} 失败: {}", file_name.to_string_lossy(), e))?;
+    }
+
+    cache.invalidate();
+
+    Ok(package_name)
+}
+
+/// 清除单个缓存包（从上游代理的包），下次请求时 Verdaccio 会重新从上游拉取
+///
+/// 根据包文档确认该包来自上游且不含本地发布的版本后才删除，避免误删已发布的包；
+/// 包文档无法读取时无法确认，直接返回错误
+#[tauri::command]
+pub async fn clear_uplink_cache(
+    cache: State<'_, PrivatePackageCache>,
+    package_name: String,
+) -> Result<(), String> {
+    validate_package_name(&package_name)?;
+
+    let storage_path = get_storage_path()?;
+    let package_path = get_package_path(&storage_path, &package_name);
+    if !package_path.exists() {
+        return Err("包不存在".to_string());
+    }
+
+    let document = read_package_document(&package_path)?;
+    if !is_uplink_cache_document(&package_name, &document) {
+        return Err(format!("{} 是私有包，不是上游缓存", package_name));
+    }
+
+    remove_package_dir(&storage_path, &package_path)?;
+    cache.invalidate();
+
+    Ok(())
+}
+
+/// 查看删除上游仓库的影响：引用它的包规则，以及经由它缓存的包数量
+#[tauri::command]
+pub async fn uplink_impact(uplink_name: String) -> Result<UplinkImpact, String> {
+    let config = load_config()?;
+    if config.get("uplinks").and_then(|u| u.get(uplink_name.as_str())).is_none() {
+        return Err(format!("上游仓库 {} 不存在", uplink_name));
+    }
+
+    let rules = config
+        .get("packages")
+        .and_then(|p| p.as_mapping())
+        .map(|packages| {
+            packages
+                .iter()
+                .filter(|(_, rule)| permission_values(rule.get("proxy")).contains(&uplink_name))
+                .filter_map(|(pattern, _)| pattern.as_str().map(|p| p.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let cached_packages = collect_package_dirs(&get_storage_path()?, false)?
+        .iter()
+        .filter(|(path, _)| {
+            read_package_document(path)
+                .ok()
+                .and_then(|doc| doc.get("_uplinks")?.get(&uplink_name).map(|_| ()))
+                .is_some()
+        })
+        .count();
+
+    Ok(UplinkImpact {
+        rules,
+        cached_packages,
+    })
+}
+
+/// 按名称列表批量删除包（对应界面中的多选删除），逐个返回成功或失败
+#[tauri::command]
+pub async fn delete_packages_by_name(
+    cache: State<'_, PrivatePackageCache>,
+    names: Vec<String>,
+) -> Result<DeletePackagesResult, String> {
+    let storage_path = get_storage_path()?;
+    let mut result = DeletePackagesResult {
+        deleted: Vec::new(),
+        failed: Vec::new(),
+    };
+
+    for name in names {
+        let outcome = validate_package_name(&name).and_then(|_| {
+            let package_path = get_package_path(&storage_path, &name);
+            if !package_path.exists() {
+                return Err("包不存在".to_string());
+            }
+            remove_package_dir(&storage_path, &package_path)
+        });
+        match outcome {
+            Ok(_) => result.deleted.push(name),
+            Err(error) => result.failed.push(DeleteFailure { name, error }),
+        }
+    }
+
+    if !result.deleted.is_empty() {
+        cache.invalidate();
+    }
+
+    Ok(result)
+}
+
+/// 清除私有包名称缓存（通过 npm 发布新包后调用，使类型筛选立即反映变化）
+#[tauri::command]
+pub async fn invalidate_private_cache(cache: State<'_, PrivatePackageCache>) -> Result<(), String> {
+    cache.invalidate();
+    Ok(())
 }
 
 /// 批量删除包
+///
+/// 按类型删除时直接请求 API 获取私有包列表而不使用缓存，避免把刚发布的私有包当作缓存包删除
 #[tauri::command]
-pub async fn delete_packages(port: u16, package_type: PackageType) -> Result<usize, String> {
-    let storage_path = get_storage_path();
-    let all_dirs = collect_package_dirs(&storage_path)?;
+pub async fn delete_packages(
+    cache: State<'_, PrivatePackageCache>,
+    port: u16,
+    package_type: PackageType,
+) -> Result<usize, String> {
+    let storage_path = get_storage_path()?;
+    let all_dirs = collect_package_dirs(&storage_path, false)?;
 
     let all_names: Vec<String> = all_dirs.into_iter().map(|(_, name)| name).collect();
-    let names_to_delete = filter_package_names_by_type(all_names, package_type, port).await?;
+    let names_to_delete = match package_type {
+        PackageType::All => all_names,
+        _ => {
+            let private_names = fetch_private_package_names(port).await?;
+            retain_package_type(all_names, package_type, &private_names)
+        }
+    };
 
     let mut deleted_count = 0;
     let mut errors = Vec::new();
 
     for name in &names_to_delete {
         let package_path = get_package_path(&storage_path, name);
-        match std::fs::remove_dir_all(&package_path) {
+        match remove_package_dir(&storage_path, &package_path) {
             Ok(_) => deleted_count += 1,
             Err(e) => errors.push(format!("{}: {}", name, e)),
         }
     }
 
+    if deleted_count > 0 {
+        cache.invalidate();
+    }
+
     if !errors.is_empty() && deleted_count == 0 {
         return Err(format!("删除失败: {}", errors.join(", ")));
     }
 
     Ok(deleted_count)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_regular_package_names() {
+        for name in ["react", "lodash.merge", "@types/node", "@scope/pkg-name"] {
+            assert!(validate_package_name(name).is_ok(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn rejects_traversal_package_names() {
+        for name in [
+            "",
+            "..",
+            "../x",
+            "x/..",
+            "@scope/../x",
+            "@../x",
+            "@scope/..",
+            "/etc/passwd",
+            "C:\\Windows",
+            "..\\x",
+            "@scope\\x",
+            "@scope/a/b",
+        ] {
+            assert!(validate_package_name(name).is_err(), "{:?}", name);
+        }
+    }
+
+    #[test]
+    fn removes_package_dir_inside_storage() {
+        let root = tempfile::tempdir().unwrap();
+        let storage = root.path().join("storage");
+        let package = storage.join("@scope").join("pkg");
+        std::fs::create_dir_all(&package).unwrap();
+
+        remove_package_dir(&storage, &package).unwrap();
+
+        assert!(!package.exists());
+        assert!(storage.join("@scope").exists());
+    }
+
+    #[test]
+    fn refuses_package_dir_outside_storage() {
+        let root = tempfile::tempdir().unwrap();
+        let storage = root.path().join("storage");
+        let outside = root.path().join("outside");
+        std::fs::create_dir_all(storage.join("pkg")).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+
+        assert!(remove_package_dir(&storage, &storage.join("..").join("outside")).is_err());
+        assert!(remove_package_dir(&storage, &storage.join("pkg").join("..")).is_err());
+        assert!(outside.exists());
+        assert!(storage.join("pkg").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn refuses_symlink_resolving_outside_storage() {
+        let root = tempfile::tempdir().unwrap();
+        let storage = root.path().join("storage");
+        let outside = root.path().join("outside");
+        std::fs::create_dir_all(&storage).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(outside.join("keep.txt"), "keep").unwrap();
+
+        let link = storage.join("linked");
+        std::os::unix::fs::symlink(&outside, &link).unwrap();
+
+        assert!(remove_package_dir(&storage, &link).is_err());
+        assert!(outside.join("keep.txt").exists());
+    }
+
+    #[test]
+    fn version_compare_orders_unparsable_versions_below_semver() {
+        use std::cmp::Ordering;
+
+        assert_eq!(version_compare("1.0.0", "latest"), Ordering::Greater);
+        assert_eq!(version_compare("1.0", "0.0.1"), Ordering::Less);
+        assert_eq!(version_compare("1.0.0-beta.1", "1.0.0"), Ordering::Less);
+        assert_eq!(version_compare("v1.0", "1.0"), Ordering::Greater);
+        assert_eq!(version_compare("1.0", "1.0"), Ordering::Equal);
+    }
+
+    #[test]
+    fn version_compare_is_a_total_order() {
+        let mut versions = vec!["2.0.0", "1.0", "latest", "1.0.0-rc.1", "v1.0", "1.0.0", "10.0", "next"];
+        versions.sort_by(|a, b| version_compare(a, b));
+        assert_eq!(
+            versions,
+            ["latest", "next", "1.0", "v1.0", "10.0", "1.0.0-rc.1", "1.0.0", "2.0.0"]
+        );
+
+        for a in &versions {
+            for b in &versions {
+                assert_eq!(version_compare(a, b), version_compare(b, a).reverse());
+            }
+        }
+    }
+
+    #[test]
+    fn rebuild_dist_tags_keeps_tags_of_surviving_versions() {
+        let versions: serde_json::Map<String, serde_json::Value> = ["1.0.0", "2.0.0-beta.1"]
+            .into_iter()
+            .map(|v| (v.to_string(), serde_json::json!({})))
+            .collect();
+        let existing = serde_json::json!({ "latest": "1.0.0", "beta": "2.0.0-beta.1", "next": "3.0.0" });
+
+        let tags = rebuild_dist_tags(Some(&existing), &versions, "1.0.0");
+
+        assert_eq!(tags, serde_json::json!({ "latest": "1.0.0", "beta": "2.0.0-beta.1" }));
+    }
+
+    #[test]
+    fn rebuild_dist_tags_replaces_missing_latest() {
+        let versions: serde_json::Map<String, serde_json::Value> =
+            [("1.0.0".to_string(), serde_json::json!({}))].into_iter().collect();
+        let existing = serde_json::json!({ "latest": "2.0.0" });
+
+        assert_eq!(
+            rebuild_dist_tags(Some(&existing), &versions, "1.0.0"),
+            serde_json::json!({ "latest": "1.0.0" })
+        );
+        assert_eq!(
+            rebuild_dist_tags(None, &versions, "1.0.0"),
+            serde_json::json!({ "latest": "1.0.0" })
+        );
+    }
+
+    #[test]
+    fn classifies_proxied_document_as_uplink_cache() {
+        let document = serde_json::json!({
+            "versions": {
+                "1.0.0": { "dist": { "tarball": "https://registry.npmjs.org/left-pad/-/left-pad-1.0.0.tgz" } }
+            },
+            "_uplinks": { "npmjs": { "fetched": 1 } },
+            "_distfiles": { "left-pad-1.0.0.tgz": { "url": "https://registry.npmjs.org/left-pad/-/left-pad-1.0.0.tgz" } }
+        });
+        assert!(is_uplink_cache_document("left-pad", &document));
+    }
+
+    #[test]
+    fn classifies_published_or_unknown_documents_as_private() {
+        let published = serde_json::json!({
+            "versions": { "1.0.0": { "dist": { "tarball": "http://localhost:4873/my-pkg/-/my-pkg-1.0.0.tgz" } } },
+            "_uplinks": {},
+            "_distfiles": {}
+        });
+        assert!(!is_uplink_cache_document("my-pkg", &published));
+
+        // 上游已有的包名下又本地发布了新版本
+        let mixed = serde_json::json!({
+            "versions": {
+                "1.0.0": { "dist": { "tarball": "https://registry.npmjs.org/my-pkg/-/my-pkg-1.0.0.tgz" } },
+                "1.0.1-local": { "dist": { "tarball": "http://localhost:4873/my-pkg/-/my-pkg-1.0.1-local.tgz" } }
+            },
+            "_uplinks": { "npmjs": {} },
+            "_distfiles": { "my-pkg-1.0.0.tgz": {} }
+        });
+        assert!(!is_uplink_cache_document("my-pkg", &mixed));
+
+        assert!(!is_uplink_cache_document("my-pkg", &serde_json::json!({ "versions": {} })));
+    }
+}
@@ -1,5 +1,14 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use tauri::{Manager, State};
+
+use super::config::{anonymous_publish_patterns, configured_port, load_config, save_config};
+use super::fsutil::{atomic_write, resolve_data_root};
+use super::verdaccio::{validate_env, validate_node_args, VerdaccioProcess};
 
 /// 应用设置
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,12 +20,81 @@ pub struct AppSettings {
     pub default_port: u16,
     #[serde(default)]
     pub allow_lan: bool,
+    /// 指定监听的 IP 地址，设置后优先于 allow_lan
+    #[serde(default)]
+    pub listen_address: Option<String>,
+    /// 界面主题：light / dark / system
+    #[serde(default = "default_theme")]
+    pub theme: String,
+    /// 界面语言，例如 zh-CN
+    #[serde(default = "default_locale")]
+    pub locale: String,
+    /// 上次检查更新的时间（RFC 3339）
+    #[serde(default)]
+    pub last_update_check: Option<String>,
+    /// HTTP 代理地址
+    #[serde(default)]
+    pub http_proxy: Option<String>,
+    /// HTTPS 代理地址
+    #[serde(default)]
+    pub https_proxy: Option<String>,
+    /// 不走代理的主机列表（逗号分隔）
+    #[serde(default)]
+    pub no_proxy: Option<String>,
+    /// 启动 Verdaccio 时额外传给 Node.js 的参数（如 --max-old-space-size=2048）
+    #[serde(default)]
+    pub node_args: Vec<String>,
+    /// 启动 Verdaccio 时注入的环境变量（供 S3 存储、认证等插件读取）
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// 包列表默认每页数量
+    #[serde(default = "default_page_size")]
+    pub default_page_size: usize,
+    /// 请求 Verdaccio API 的超时时间（毫秒）
+    #[serde(default = "default_api_timeout_ms")]
+    pub api_timeout_ms: u64,
+    /// 详细日志模式：启动时注入 NODE_DEBUG / DEBUG 环境变量，配置日志级别为 debug
+    #[serde(default)]
+    pub verbose_mode: bool,
+    /// 开启详细日志模式前配置中的 `log.level`（未配置时为 None），关闭时据此恢复
+    #[serde(default)]
+    pub verbose_previous_log_level: Option<String>,
+    /// 托盘提示文字模板，支持 `{status}`、`{port}` 占位符
+    #[serde(default = "default_tray_tooltip_template")]
+    pub tray_tooltip_template: String,
+    /// 设置文件损坏并已被重置为默认值（仅用于通知前端，不会持久化）
+    #[serde(default, skip_deserializing, skip_serializing_if = "std::ops::Not::not")]
+    pub settings_was_reset: bool,
 }
 
 fn default_port() -> u16 {
     4873
 }
 
+fn default_page_size() -> usize {
+    20
+}
+
+/// API 请求超时时间的允许范围（毫秒），过小会让所有私有包请求超时
+const MIN_API_TIMEOUT_MS: u64 = 500;
+const MAX_API_TIMEOUT_MS: u64 = 60_000;
+
+fn default_api_timeout_ms() -> u64 {
+    5000
+}
+
+fn default_tray_tooltip_template() -> String {
+    "Verdaccio {status} :{port}".to_string()
+}
+
+fn default_theme() -> String {
+    "system".to_string()
+}
+
+fn default_locale() -> String {
+    "zh-CN".to_string()
+}
+
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
@@ -25,19 +103,94 @@ impl Default for AppSettings {
             auto_start_verdaccio: false,
             default_port: 4873,
             allow_lan: false,
+            listen_address: None,
+            theme: default_theme(),
+            locale: default_locale(),
+            last_update_check: None,
+            http_proxy: None,
+            https_proxy: None,
+            no_proxy: None,
+            node_args: Vec::new(),
+            env: HashMap::new(),
+            default_page_size: default_page_size(),
+            api_timeout_ms: default_api_timeout_ms(),
+            verbose_mode: false,
+            verbose_previous_log_level: None,
+            tray_tooltip_template: default_tray_tooltip_template(),
+            settings_was_reset: false,
+        }
+    }
+}
+
+/// 托盘提示文字模板支持的占位符
+const TRAY_TOOLTIP_PLACEHOLDERS: [&str; 2] = ["status", "port"];
+
+/// 校验托盘提示文字模板（只允许已知占位符，花括号需成对出现）
+fn validate_tray_tooltip_template(template: &str) -> Result<(), String> {
+    let placeholder_re = regex::Regex::new(r"\{([^{}]*)\}").unwrap();
+    if let Some(unknown) = placeholder_re
+        .captures_iter(template)
+        .map(|caps| caps[1].to_string())
+        .find(|name| !TRAY_TOOLTIP_PLACEHOLDERS.contains(&name.as_str()))
+    {
+        return Err(format!("未知的托盘提示占位符: {{{}}}", unknown));
+    }
+    if placeholder_re.replace_all(template, "").contains(['{', '}']) {
+        return Err("托盘提示模板中的花括号不成对".to_string());
+    }
+    Ok(())
+}
+
+/// 托盘提示文字模板缓存
+///
+/// 后台状态轮询从这里读取模板，不再反复读取设置文件；保存或导入设置时同步更新
+pub struct TrayTooltipTemplate(Mutex<String>);
+
+impl Default for TrayTooltipTemplate {
+    fn default() -> Self {
+        Self(Mutex::new(default_tray_tooltip_template()))
+    }
+}
+
+impl TrayTooltipTemplate {
+    /// 更新缓存的模板，模板无效时使用默认模板
+    pub fn set(&self, template: &str) {
+        let template = if validate_tray_tooltip_template(template).is_ok() {
+            template.to_string()
+        } else {
+            default_tray_tooltip_template()
+        };
+        if let Ok(mut cached) = self.0.lock() {
+            *cached = template;
         }
     }
+
+    /// 按缓存的模板生成托盘提示文字
+    pub fn render(&self, status: &str, port: u16) -> String {
+        let template = self
+            .0
+            .lock()
+            .map(|template| template.clone())
+            .unwrap_or_else(|_| default_tray_tooltip_template());
+        template
+            .replace("{status}", status)
+            .replace("{port}", &port.to_string())
+    }
 }
 
 /// 获取设置文件路径
-fn get_settings_path() -> PathBuf {
-    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    home.join(".mint-verdaccio").join("settings.json")
+pub(crate) fn get_settings_path() -> Result<PathBuf, String> {
+    Ok(resolve_data_root()?.join(".mint-verdaccio").join("settings.json"))
+}
+
+/// 获取损坏设置文件的备份路径
+fn get_corrupt_settings_path() -> Result<PathBuf, String> {
+    Ok(get_settings_path()?.with_extension("json.corrupt"))
 }
 
 /// 确保设置目录存在
 fn ensure_settings_dir() -> Result<(), String> {
-    let settings_path = get_settings_path();
+    let settings_path = get_settings_path()?;
     if let Some(parent) = settings_path.parent() {
         if !parent.exists() {
             std::fs::create_dir_all(parent)
@@ -47,35 +200,331 @@ fn ensure_settings_dir() -> Result<(), String> {
     Ok(())
 }
 
-/// 获取应用设置
-#[tauri::command]
-pub async fn get_app_settings() -> Result<AppSettings, String> {
-    let settings_path = get_settings_path();
+/// 设置文件已被重置但尚未通知前端
+///
+/// 后台任务（如启动时读取端口）也会读取设置，重置标记不能只在触发重置的那次读取中返回
+static SETTINGS_RESET_PENDING: AtomicBool = AtomicBool::new(false);
+
+/// 读取应用设置
+///
+/// 设置文件无法解析时备份为 settings.json.corrupt 并返回默认设置，而不是让前端拿不到任何设置；
+/// 首次启动（设置文件不存在）时默认端口取自已有 Verdaccio 配置中的 `listen`
+pub(crate) fn load_app_settings() -> Result<AppSettings, String> {
+    let settings_path = get_settings_path()?;
     
     if !settings_path.exists() {
-        return Ok(AppSettings::default());
+        let default_port = load_config()
+            .ok()
+            .and_then(|config| configured_port(&config))
+            .unwrap_or_else(default_port);
+        return Ok(AppSettings {
+            default_port,
+            ..AppSettings::default()
+        });
     }
     
     let content = std::fs::read_to_string(&settings_path)
         .map_err(|e| format!("读取设置文件失败: {}", e))?;
     
-    let settings: AppSettings = serde_json::from_str(&content)
-        .map_err(|e| format!("解析设置文件失败: {}", e))?;
+    match serde_json::from_str::<AppSettings>(&content) {
+        Ok(settings) => Ok(settings),
+        Err(_) => {
+            std::fs::rename(&settings_path, get_corrupt_settings_path()?)
+                .map_err(|e| format!("备份损坏的设置文件失败: {}", e))?;
+            SETTINGS_RESET_PENDING.store(true, Ordering::Relaxed);
+            Ok(AppSettings::default())
+        }
+    }
+}
+
+/// 写入应用设置
+pub(crate) fn write_app_settings(settings: &AppSettings) -> Result<(), String> {
+    ensure_settings_dir()?;
+    
+    let settings_path = get_settings_path()?;
+    let content = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("序列化设置失败: {}", e))?;
+    
+    atomic_write(&settings_path, content)
+        .map_err(|e| format!("保存设置文件失败: {}", e))
+}
+
+/// 创建已按代理设置配置好的 HTTP 客户端构建器
+///
+/// 本机地址（localhost / 127.0.0.1）始终直连，以免本地 Verdaccio API 请求被转发到代理
+pub(crate) fn http_client_builder() -> Result<reqwest::ClientBuilder, String> {
+    let settings = load_app_settings()?;
+    let mut builder = reqwest::Client::builder();
+
+    let no_proxy = reqwest::NoProxy::from_string(&format!(
+        "localhost,127.0.0.1,::1,{}",
+        settings.no_proxy.as_deref().unwrap_or_default()
+    ));
+
+    if let Some(http_proxy) = settings.http_proxy.as_deref().filter(|p| !p.is_empty()) {
+        let proxy = reqwest::Proxy::http(http_proxy)
+            .map_err(|e| format!("无效的 HTTP 代理地址: {}", e))?
+            .no_proxy(no_proxy.clone());
+        builder = builder.proxy(proxy);
+    }
+    if let Some(https_proxy) = settings.https_proxy.as_deref().filter(|p| !p.is_empty()) {
+        let proxy = reqwest::Proxy::https(https_proxy)
+            .map_err(|e| format!("无效的 HTTPS 代理地址: {}", e))?
+            .no_proxy(no_proxy);
+        builder = builder.proxy(proxy);
+    }
+
+    Ok(builder)
+}
+
+/// 获取应用设置
+#[tauri::command]
+pub async fn get_app_settings(process: State<'_, VerdaccioProcess>) -> Result<AppSettings, String> {
+    let mut settings = load_app_settings()?;
+    settings.settings_was_reset = SETTINGS_RESET_PENDING.swap(false, Ordering::Relaxed);
+
+    if settings.settings_was_reset {
+        process.add_log(
+            "WARN",
+            format!(
+                "设置文件已损坏，已备份为 {} 并恢复默认设置",
+                get_corrupt_settings_path()?.display()
+            ),
+        );
+    }
     
     Ok(settings)
 }
 
+/// 校验应用设置中各字段的取值
+fn validate_app_settings(settings: &AppSettings) -> Result<(), String> {
+    if settings.default_port == 0 {
+        return Err("默认端口必须在 1 到 65535 之间".to_string());
+    }
+    if !(1..=200).contains(&settings.default_page_size) {
+        return Err("每页数量需在 1 到 200 之间".to_string());
+    }
+    if !(MIN_API_TIMEOUT_MS..=MAX_API_TIMEOUT_MS).contains(&settings.api_timeout_ms) {
+        return Err(format!(
+            "API 超时时间需在 {} 到 {} 毫秒之间",
+            MIN_API_TIMEOUT_MS, MAX_API_TIMEOUT_MS
+        ));
+    }
+    validate_node_args(&settings.node_args)?;
+    validate_env(&settings.env)?;
+    validate_tray_tooltip_template(&settings.tray_tooltip_template)
+}
+
 /// 保存应用设置
+///
+/// 传入的字段合并到当前设置上，未传入的字段保持不变，避免只提交部分表单字段时重置其余设置
 #[tauri::command]
-pub async fn save_app_settings(settings: AppSettings) -> Result<(), String> {
-    ensure_settings_dir()?;
-    
-    let settings_path = get_settings_path();
+pub async fn save_app_settings(
+    tray_template: State<'_, TrayTooltipTemplate>,
+    settings: serde_json::Map<String, serde_json::Value>,
+) -> Result<(), String> {
+    let mut merged = serde_json::to_value(load_app_settings()?)
+        .map_err(|e| format!("序列化设置失败: {}", e))?;
+    if let Some(current) = merged.as_object_mut() {
+        current.extend(settings);
+    }
+    let settings: AppSettings =
+        serde_json::from_value(merged).map_err(|e| format!("解析设置失败: {}", e))?;
+
+    validate_app_settings(&settings)?;
+    write_app_settings(&settings)?;
+    tray_template.set(&settings.tray_tooltip_template);
+    Ok(())
+}
+
+/// 将当前应用设置导出为 JSON 文件，便于迁移到其他机器
+#[tauri::command]
+pub async fn export_settings(path: String) -> Result<(), String> {
+    let settings = load_app_settings()?;
     let content = serde_json::to_string_pretty(&settings)
         .map_err(|e| format!("序列化设置失败: {}", e))?;
-    
-    std::fs::write(&settings_path, content)
-        .map_err(|e| format!("保存设置文件失败: {}", e))
+
+    std::fs::write(&path, content).map_err(|e| format!("导出设置失败: {}", e))
+}
+
+/// 从 JSON 文件导入应用设置（校验通过后覆盖当前设置），并按导入的 `auto_start` 同步开机自启
+///
+/// 返回导入后的设置，前端据此刷新界面
+#[tauri::command]
+pub async fn import_settings(app_handle: tauri::AppHandle, path: String) -> Result<AppSettings, String> {
+    let content = std::fs::read_to_string(&path).map_err(|e| format!("读取设置文件失败: {}", e))?;
+    let settings: AppSettings =
+        serde_json::from_str(&content).map_err(|e| format!("解析设置文件失败: {}", e))?;
+
+    validate_app_settings(&settings)?;
+    write_app_settings(&settings)?;
+    app_handle
+        .state::<TrayTooltipTemplate>()
+        .set(&settings.tray_tooltip_template);
+
+    set_auto_start(app_handle, settings.auto_start).await?;
+
+    Ok(settings)
+}
+
+/// 设置托盘提示文字模板（支持 `{status}`、`{port}`），传入空字符串时恢复默认模板
+///
+/// 后台状态轮询会在下一个周期应用新模板
+#[tauri::command]
+pub async fn set_tray_tooltip_template(
+    tray_template: State<'_, TrayTooltipTemplate>,
+    template: String,
+) -> Result<(), String> {
+    let template = if template.trim().is_empty() {
+        default_tray_tooltip_template()
+    } else {
+        template
+    };
+    validate_tray_tooltip_template(&template)?;
+
+    let mut settings = load_app_settings()?;
+    settings.tray_tooltip_template = template;
+    write_app_settings(&settings)?;
+    tray_template.set(&settings.tray_tooltip_template);
+    Ok(())
+}
+
+/// 获取是否允许局域网访问
+#[tauri::command]
+pub async fn get_allow_lan() -> Result<bool, String> {
+    let settings = load_app_settings()?;
+    Ok(settings.allow_lan)
+}
+
+/// 设置是否允许局域网访问
+///
+/// 开启时若配置中存在允许匿名发布（`$all` / `$anonymous`）的包规则，返回安全警告
+#[tauri::command]
+pub async fn set_allow_lan(enabled: bool) -> Result<Option<String>, String> {
+    let mut settings = load_app_settings()?;
+    settings.allow_lan = enabled;
+    write_app_settings(&settings)?;
+
+    if !enabled {
+        return Ok(None);
+    }
+
+    // 配置文件不存在或无法解析时不阻止设置，仅跳过检查
+    let patterns = match load_config() {
+        Ok(config) => anonymous_publish_patterns(&config),
+        Err(_) => return Ok(None),
+    };
+
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(format!(
+        "以下包规则允许匿名发布: {}，开启局域网访问后局域网内的任何人都可以向仓库发布包",
+        patterns.join(", ")
+    )))
+}
+
+/// 设置代理
+///
+/// 同时保存到应用设置（用于应用自身的请求）并写入 Verdaccio 配置中每个上游仓库，
+/// 使 Verdaccio 访问上游时也走同样的代理
+#[tauri::command]
+pub async fn set_proxy_settings(
+    http_proxy: Option<String>,
+    https_proxy: Option<String>,
+    no_proxy: Option<String>,
+) -> Result<(), String> {
+    let non_empty = |v: Option<String>| v.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    let http_proxy = non_empty(http_proxy);
+    let https_proxy = non_empty(https_proxy);
+    let no_proxy = non_empty(no_proxy);
+
+    for proxy in [&http_proxy, &https_proxy].into_iter().flatten() {
+        reqwest::Proxy::all(proxy.as_str()).map_err(|e| format!("无效的代理地址 {}: {}", proxy, e))?;
+    }
+
+    let mut settings = load_app_settings()?;
+    settings.http_proxy = http_proxy.clone();
+    settings.https_proxy = https_proxy.clone();
+    settings.no_proxy = no_proxy.clone();
+    write_app_settings(&settings)?;
+
+    let mut config = load_config()?;
+    if let Some(uplinks) = config.get_mut("uplinks").and_then(|u| u.as_mapping_mut()) {
+        for uplink in uplinks.values_mut().filter_map(|u| u.as_mapping_mut()) {
+            for (key, value) in [
+                ("http_proxy", &http_proxy),
+                ("https_proxy", &https_proxy),
+                ("no_proxy", &no_proxy),
+            ] {
+                match value {
+                    Some(v) => {
+                        uplink.insert(key.into(), serde_yaml::Value::String(v.clone()));
+                    }
+                    None => {
+                        uplink.shift_remove(key);
+                    }
+                }
+            }
+        }
+    }
+
+    save_config(&config)
+}
+
+/// 开启或关闭详细日志模式，返回是否需要重启 Verdaccio 才能生效
+///
+/// 开启时记住配置中原有的 `log.level` 并将其设为 `debug`，关闭时恢复原有级别（原先未配置则移除）；
+/// 调试环境变量在下次启动 Verdaccio 时注入
+#[tauri::command]
+pub async fn set_verbose_mode(
+    process: State<'_, VerdaccioProcess>,
+    enabled: bool,
+) -> Result<bool, String> {
+    let mut settings = load_app_settings()?;
+    let changed = settings.verbose_mode != enabled;
+
+    let mut config = load_config()?;
+    let root = config
+        .as_mapping_mut()
+        .ok_or_else(|| "配置文件格式无效".to_string())?;
+    let log = root
+        .entry("log".into())
+        .or_insert_with(|| serde_yaml::Value::Mapping(serde_yaml::Mapping::new()))
+        .as_mapping_mut()
+        .ok_or_else(|| "log 配置格式无效".to_string())?;
+    let current_level = log.get("level").and_then(|l| l.as_str()).map(String::from);
+
+    let new_level = if enabled {
+        if changed {
+            settings.verbose_previous_log_level = current_level.clone();
+        }
+        Some("debug".to_string())
+    } else if changed {
+        settings.verbose_previous_log_level.take()
+    } else {
+        // 本来就未开启，保持用户当前的级别
+        current_level.clone()
+    };
+
+    let level_changed = new_level != current_level;
+    match &new_level {
+        Some(level) => {
+            log.insert("level".into(), serde_yaml::Value::String(level.clone()));
+        }
+        None => {
+            log.shift_remove("level");
+        }
+    }
+    if level_changed {
+        save_config(&config)?;
+    }
+
+    settings.verbose_mode = enabled;
+    write_app_settings(&settings)?;
+
+    Ok((changed || level_changed) && process.check_running())
 }
 
 /// 设置开机自启
@@ -94,6 +543,124 @@ pub async fn set_auto_start(app_handle: tauri::AppHandle, enable: bool) -> Resul
     }
 }
 
+/// 开机自启参数一致性检查结果
+#[derive(Debug, Clone, Serialize)]
+pub struct AutostartCheck {
+    /// 是否已启用开机自启
+    pub enabled: bool,
+    /// 系统中登记的启动命令（无法读取时为 None）
+    pub registered_command: Option<String>,
+    /// 登记的启动命令是否带有 `--minimized`
+    pub minimized: bool,
+    /// 是否已重新登记以修复参数
+    pub repaired: bool,
+}
+
+/// 开机自启时传给应用的参数（与插件初始化时的配置保持一致）
+pub(crate) const AUTOSTART_MINIMIZED_ARG: &str = "--minimized";
+
+/// 读取系统中登记的开机自启命令（Linux 为 .desktop 的 Exec，macOS 为 LaunchAgent plist，
+/// Windows 为注册表 Run 项）
+fn registered_autostart_command(app_name: &str) -> Option<String> {
+    #[cfg(target_os = "linux")]
+    {
+        let path = dirs::home_dir()?
+            .join(".config")
+            .join("autostart")
+            .join(format!("{}.desktop", app_name));
+        let content = std::fs::read_to_string(path).ok()?;
+        content
+            .lines()
+            .find_map(|line| line.strip_prefix("Exec="))
+            .map(|exec| exec.to_string())
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let path = dirs::home_dir()?
+            .join("Library")
+            .join("LaunchAgents")
+            .join(format!("{}.plist", app_name));
+        std::fs::read_to_string(path).ok()
+    }
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+        let output = std::process::Command::new("reg")
+            .args([
+                "query",
+                r"HKCU\SOFTWARE\Microsoft\Windows\CurrentVersion\Run",
+                "/v",
+                app_name,
+            ])
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find(|line| line.trim_start().starts_with(app_name))
+            .and_then(|line| line.split_once("REG_SZ"))
+            .map(|(_, command)| command.trim().to_string())
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+    {
+        let _ = app_name;
+        None
+    }
+}
+
+/// 检查开机自启登记的命令是否带有 `--minimized`，不一致时重新登记（先禁用再启用）
+///
+/// 未启用开机自启时不做任何修改
+#[tauri::command]
+pub async fn ensure_autostart_minimized(app_handle: tauri::AppHandle) -> Result<AutostartCheck, String> {
+    use tauri_plugin_autostart::ManagerExt;
+
+    let autostart_manager = app_handle.autolaunch();
+    let app_name = app_handle.package_info().name.clone();
+
+    let enabled = autostart_manager
+        .is_enabled()
+        .map_err(|e| format!("获取开机自启状态失败: {}", e))?;
+    let has_minimized = |command: &Option<String>| {
+        command
+            .as_deref()
+            .is_some_and(|c| c.contains(AUTOSTART_MINIMIZED_ARG))
+    };
+
+    let registered_command = registered_autostart_command(&app_name);
+    if !enabled || has_minimized(&registered_command) {
+        return Ok(AutostartCheck {
+            enabled,
+            minimized: has_minimized(&registered_command),
+            registered_command,
+            repaired: false,
+        });
+    }
+
+    autostart_manager
+        .disable()
+        .map_err(|e| format!("取消开机自启失败: {}", e))?;
+    autostart_manager
+        .enable()
+        .map_err(|e| format!("设置开机自启失败: {}", e))?;
+
+    let registered_command = registered_autostart_command(&app_name);
+    Ok(AutostartCheck {
+        enabled: true,
+        minimized: has_minimized(&registered_command),
+        registered_command,
+        repaired: true,
+    })
+}
+
 /// 获取开机自启状态
 #[tauri::command]
 pub async fn get_auto_start_status(app_handle: tauri::AppHandle) -> Result<bool, String> {
@@ -0,0 +1,308 @@
+use serde::Serialize;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use super::packages::{dir_size, get_storage_path};
+use super::settings::get_settings_path;
+use super::verdaccio::get_config_path;
+
+/// 临时文件、锁文件及缺少 package.json 的目录超过该时间未更新才视为遗留，避免误删正在进行的发布
+const STALE_AFTER: Duration = Duration::from_secs(10 * 60);
+
+/// 存储问题类型
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageIssueKind {
+    /// package.json 为空文件
+    EmptyPackageJson,
+    /// 崩溃后遗留的临时文件
+    TempFile,
+    /// 遗留的锁文件
+    LockFile,
+    /// 包目录中缺少 package.json
+    MissingPackageJson,
+}
+
+/// 存储问题
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageIssue {
+    pub kind: StorageIssueKind,
+    pub path: String,
+    pub size_bytes: u64,
+}
+
+/// 目录类型
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DirKind {
+    /// Verdaccio 配置目录（config.yaml 所在目录）
+    Config,
+    /// 包存储目录
+    Storage,
+    /// 应用设置目录
+    Settings,
+}
+
+/// 目录写入权限检查结果
+#[derive(Debug, Clone, Serialize)]
+pub struct DirPermission {
+    pub kind: DirKind,
+    pub path: String,
+    pub writable: bool,
+    /// 不可写时的错误信息
+    pub error: Option<String>,
+}
+
+/// 存储目录所在磁盘的空间信息
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageDiskSpace {
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+    pub used_by_storage_bytes: u64,
+}
+
+/// 在目录中创建并删除一个测试文件，确认目录可写（目录不存在时先尝试创建）
+fn check_dir_writable(dir: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("创建目录失败: {}", e))?;
+
+    let test_path = dir.join(format!(".mint-verdaccio-write-test.{}", std::process::id()));
+    std::fs::write(&test_path, b"").map_err(|e| format!("写入测试文件失败: {}", e))?;
+    std::fs::remove_file(&test_path).map_err(|e| format!("删除测试文件失败: {}", e))
+}
+
+/// 判断文件名是否为临时文件
+fn is_temp_file(name: &str) -> bool {
+    name.starts_with(".tmp") || name.ends_with(".tmp") || name.contains(".tmp-")
+}
+
+/// 判断文件是否长时间未更新
+fn is_stale(metadata: &std::fs::Metadata) -> bool {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age > STALE_AFTER)
+}
+
+/// 判断目录及其中的文件是否都长时间未更新（发布过程中目录内会不断写入新文件）
+fn is_dir_stale(path: &Path) -> bool {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return false;
+    };
+    if !is_stale(&metadata) {
+        return false;
+    }
+    std::fs::read_dir(path).is_ok_and(|entries| {
+        entries
+            .flatten()
+            .all(|entry| entry.metadata().is_ok_and(|m| is_stale(&m)))
+    })
+}
+
+/// 检查单个包目录中的问题
+fn scan_package_dir(path: &Path, issues: &mut Vec<StorageIssue>) {
+    let package_json = path.join("package.json");
+    match std::fs::metadata(&package_json) {
+        Ok(m) if m.len() == 0 => issues.push(StorageIssue {
+            kind: StorageIssueKind::EmptyPackageJson,
+            path: package_json.to_string_lossy().to_string(),
+            size_bytes: 0,
+        }),
+        Ok(_) => {}
+        // 最近仍有写入的目录可能是正在发布的包，暂不视为问题
+        Err(_) if !is_dir_stale(path) => return,
+        Err(_) => {
+            let size_bytes = std::fs::read_dir(path)
+                .map(|entries| {
+                    entries
+                        .flatten()
+                        .filter_map(|e| e.metadata().ok())
+                        .map(|m| m.len())
+                        .sum()
+                })
+                .unwrap_or(0);
+            issues.push(StorageIssue {
+                kind: StorageIssueKind::MissingPackageJson,
+                path: path.to_string_lossy().to_string(),
+                size_bytes,
+            });
+            // 整个目录都会被清理，无需再检查其中的文件
+            return;
+        }
+    }
+
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let kind = if is_temp_file(&name) {
+            StorageIssueKind::TempFile
+        } else if name.ends_with(".lock") {
+            StorageIssueKind::LockFile
+        } else {
+            continue;
+        };
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if is_stale(&metadata) {
+            issues.push(StorageIssue {
+                kind,
+                path: entry.path().to_string_lossy().to_string(),
+                size_bytes: metadata.len(),
+            });
+        }
+    }
+}
+
+/// 扫描存储目录中的问题
+fn collect_storage_issues(storage_path: &Path) -> Result<Vec<StorageIssue>, String> {
+    let mut issues = Vec::new();
+
+    if !storage_path.exists() {
+        return Ok(issues);
+    }
+
+    let entries = std::fs::read_dir(storage_path)
+        .map_err(|e| format!("读取存储目录失败: {}", e))?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        if !path.is_dir() {
+            match entry.metadata() {
+                Ok(metadata) if is_temp_file(&name) && is_stale(&metadata) => {
+                    issues.push(StorageIssue {
+                        kind: StorageIssueKind::TempFile,
+                        path: path.to_string_lossy().to_string(),
+                        size_bytes: metadata.len(),
+                    });
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        // 跳过 Verdaccio 内部的隐藏目录
+        if name.starts_with('.') {
+            continue;
+        }
+
+        if name.starts_with('@') {
+            if let Ok(scoped_entries) = std::fs::read_dir(&path) {
+                for scoped_entry in scoped_entries.flatten() {
+                    if scoped_entry.path().is_dir() {
+                        scan_package_dir(&scoped_entry.path(), &mut issues);
+                    }
+                }
+            }
+            continue;
+        }
+
+        scan_package_dir(&path, &mut issues);
+    }
+
+    Ok(issues)
+}
+
+/// 扫描存储目录中的异常文件（空 package.json、临时文件、锁文件、缺少 package.json 的包目录）
+#[tauri::command]
+pub async fn scan_storage_issues() -> Result<Vec<StorageIssue>, String> {
+    collect_storage_issues(&get_storage_path()?)
+}
+
+/// 清理存储目录中的异常文件，`dry_run` 为 true 时只返回将被清理的项目
+///
+/// 缺少 package.json 的目录在删除前会再次确认仍未更新，跳过扫描后开始写入的目录
+#[tauri::command]
+pub async fn clean_storage_issues(dry_run: bool) -> Result<Vec<StorageIssue>, String> {
+    let issues = collect_storage_issues(&get_storage_path()?)?;
+
+    if dry_run {
+        return Ok(issues);
+    }
+
+    let mut cleaned = Vec::with_capacity(issues.len());
+    for issue in issues {
+        let result = match issue.kind {
+            StorageIssueKind::MissingPackageJson => {
+                let path = Path::new(&issue.path);
+                if path.join("package.json").exists() || !is_dir_stale(path) {
+                    continue;
+                }
+                std::fs::remove_dir_all(path)
+            }
+            _ => std::fs::remove_file(&issue.path),
+        };
+        if result.is_ok() {
+            cleaned.push(issue);
+        }
+    }
+
+    Ok(cleaned)
+}
+
+/// 检查配置目录、存储目录与设置目录是否可写，便于在发布或添加用户失败前提示用户修复权限
+#[tauri::command]
+pub async fn check_permissions() -> Result<Vec<DirPermission>, String> {
+    let config_dir = get_config_path()?
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| "无效的配置文件路径".to_string())?;
+    let settings_dir = get_settings_path()?
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| "无效的设置文件路径".to_string())?;
+
+    Ok([
+        (DirKind::Config, config_dir),
+        (DirKind::Storage, get_storage_path()?),
+        (DirKind::Settings, settings_dir),
+    ]
+    .into_iter()
+    .map(|(kind, dir)| {
+        let result = check_dir_writable(&dir);
+        DirPermission {
+            kind,
+            path: dir.to_string_lossy().to_string(),
+            writable: result.is_ok(),
+            error: result.err(),
+        }
+    })
+    .collect())
+}
+
+/// 获取存储目录所在磁盘的总空间、可用空间及存储目录占用的空间，便于在批量操作前提示磁盘将满
+#[tauri::command]
+pub async fn get_storage_disk_space() -> Result<StorageDiskSpace, String> {
+    let storage_path = get_storage_path()?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        // 存储目录尚未创建时按最近的已存在上级目录查找所在磁盘
+        let existing = storage_path
+            .ancestors()
+            .find(|path| path.exists())
+            .ok_or_else(|| format!("存储目录不存在: {}", storage_path.display()))?;
+        let resolved = existing
+            .canonicalize()
+            .map_err(|e| format!("解析存储目录失败: {}", e))?;
+
+        let disks = sysinfo::Disks::new_with_refreshed_list();
+        let disk = disks
+            .list()
+            .iter()
+            .filter(|disk| resolved.starts_with(disk.mount_point()))
+            .max_by_key(|disk| disk.mount_point().components().count())
+            .ok_or_else(|| format!("未找到存储目录所在的磁盘: {}", resolved.display()))?;
+
+        Ok(StorageDiskSpace {
+            total_bytes: disk.total_space(),
+            available_bytes: disk.available_space(),
+            used_by_storage_bytes: dir_size(&storage_path),
+        })
+    })
+    .await
+    .map_err(|e| format!("获取磁盘空间失败: {}", e))?
+}
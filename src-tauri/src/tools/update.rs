@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+use super::packages::version_compare;
+use super::settings::{http_client_builder, load_app_settings, write_app_settings};
+
+/// GitHub 最新发布版本 API
+const RELEASES_API_URL: &str =
+    "https://api.github.com/repos/MitsuhaYuki/mint-verdaccio-manager/releases/latest";
+
+/// 两次检查更新之间的最小间隔（小时）
+const UPDATE_CHECK_INTERVAL_HOURS: i64 = 6;
+
+/// 更新检查结果
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateInfo {
+    /// 是否实际请求了 GitHub（距上次检查过近或网络不可用时为 false）
+    pub checked: bool,
+    pub update_available: bool,
+    pub current_version: String,
+    pub latest_version: Option<String>,
+    pub release_url: Option<String>,
+}
+
+/// GitHub API 返回的发布信息
+#[derive(Debug, Clone, Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    html_url: String,
+}
+
+/// 判断距上次检查是否已超过最小间隔
+fn should_check(last_update_check: Option<&str>) -> bool {
+    let Some(last) = last_update_check else {
+        return true;
+    };
+
+    match chrono::DateTime::parse_from_rfc3339(last) {
+        Ok(last) => {
+            chrono::Local::now().signed_duration_since(last)
+                >= chrono::Duration::hours(UPDATE_CHECK_INTERVAL_HOURS)
+        }
+        Err(_) => true,
+    }
+}
+
+/// 请求 GitHub 获取最新发布版本
+async fn fetch_latest_release() -> Result<GithubRelease, String> {
+    let client = http_client_builder()?
+        .timeout(std::time::Duration::from_secs(5))
+        .user_agent(concat!("mint-verdaccio/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| format!("创建 HTTP 客户端失败: {}", e))?;
+
+    let response = client
+        .get(RELEASES_API_URL)
+        .send()
+        .await
+        .map_err(|e| format!("请求失败: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("请求失败: HTTP {}", response.status()));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("解析响应失败: {}", e))
+}
+
+/// 检查应用更新
+#[tauri::command]
+pub async fn check_for_update() -> Result<UpdateInfo, String> {
+    let current_version = env!("CARGO_PKG_VERSION").to_string();
+    let mut info = UpdateInfo {
+        checked: false,
+        update_available: false,
+        current_version: current_version.clone(),
+        latest_version: None,
+        release_url: None,
+    };
+
+    let mut settings = load_app_settings()?;
+    if !should_check(settings.last_update_check.as_deref()) {
+        return Ok(info);
+    }
+
+    // 离线或 API 不可用时静默返回，不视为错误
+    let Ok(release) = fetch_latest_release().await else {
+        return Ok(info);
+    };
+
+    settings.last_update_check = Some(chrono::Local::now().to_rfc3339());
+    write_app_settings(&settings)?;
+
+    let latest_version = release.tag_name.trim_start_matches('v').to_string();
+    info.checked = true;
+    info.update_available =
+        version_compare(&latest_version, &current_version) == std::cmp::Ordering::Greater;
+    info.latest_version = Some(latest_version);
+    info.release_url = Some(release.html_url);
+
+    Ok(info)
+}
@@ -1,18 +1,218 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tauri::State;
+
+use super::config::{configured_groups, is_valid_group_name, load_config};
+use super::des_crypt::{des_crypt, CRYPT_ALPHABET};
+use super::fsutil::{atomic_write, resolve_data_root};
+use super::settings::{http_client_builder, load_app_settings};
+use super::verdaccio::get_config_path;
 
 /// 用户信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserInfo {
     pub username: String,
     pub created: Option<String>,
+    pub disabled: bool,
+    pub hash_type: HashType,
+}
+
+/// htpasswd 密码哈希类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashType {
+    Bcrypt,
+    Apr1,
+    Sha1,
+    Crypt,
+    Unknown,
+}
+
+/// 用户数量达到 `max_users` 上限时返回的错误前缀，前端据此禁用添加按钮
+pub const USER_LIMIT_REACHED_ERROR: &str = "UserLimitReached";
+
+/// 清空用户数据库时需要输入的确认文字
+pub const RESET_USERS_CONFIRMATION: &str = "RESET USERS";
+
+/// 禁用用户时添加到密码哈希前的标记，带标记的哈希不会匹配任何密码
+const DISABLED_HASH_PREFIX: char = '!';
+
+/// Apache MD5 哈希的前缀
+const APR1_MAGIC: &str = "$apr1$";
+
+/// 等待 htpasswd.lock 锁文件释放的最大重试次数
+const LOCK_FILE_RETRIES: u32 = 50;
+/// 每次重试之间的等待时间
+const LOCK_FILE_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+/// 锁文件超过该时间未更新视为残留，可直接清除
+const LOCK_FILE_STALE_AFTER: Duration = Duration::from_secs(10);
+
+/// htpasswd 读写锁
+///
+/// 进程内使用异步互斥锁串行化读-改-写操作；同时创建与 Verdaccio 一致的 `htpasswd.lock`
+/// 锁文件，避免与 `npm adduser` 等经由 Verdaccio 的写入互相覆盖
+#[derive(Default)]
+pub struct HtpasswdLock(tokio::sync::Mutex<()>);
+
+/// 持有期间 htpasswd 处于锁定状态，释放时删除锁文件
+pub struct HtpasswdGuard<'a> {
+    lock_file: PathBuf,
+    _guard: tokio::sync::MutexGuard<'a, ()>,
+}
+
+impl Drop for HtpasswdGuard<'_> {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.lock_file);
+    }
+}
+
+impl HtpasswdLock {
+    /// 获取 htpasswd 文件锁（等待期间让出异步运行时，不阻塞工作线程）
+    pub async fn acquire(&self, htpasswd_path: &Path) -> Result<HtpasswdGuard<'_>, String> {
+        let guard = self.0.lock().await;
+
+        let lock_file = match htpasswd_path.file_name() {
+            Some(name) => htpasswd_path.with_file_name(format!("{}.lock", name.to_string_lossy())),
+            None => return Err("无效的 htpasswd 文件路径".to_string()),
+        };
+
+        for _ in 0..LOCK_FILE_RETRIES {
+            match std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&lock_file)
+            {
+                Ok(_) => {
+                    return Ok(HtpasswdGuard {
+                        lock_file,
+                        _guard: guard,
+                    })
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    if is_stale_lock_file(&lock_file) {
+                        let _ = std::fs::remove_file(&lock_file);
+                        continue;
+                    }
+                    tokio::time::sleep(LOCK_FILE_RETRY_INTERVAL).await;
+                }
+                Err(e) => return Err(format!("创建 htpasswd 锁文件失败: {}", e)),
+            }
+        }
+
+        Err("htpasswd 文件正被其他程序占用，请稍后重试".to_string())
+    }
+}
+
+/// 判断锁文件是否为崩溃等原因遗留的过期锁
+fn is_stale_lock_file(lock_file: &Path) -> bool {
+    std::fs::metadata(lock_file)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age > LOCK_FILE_STALE_AFTER)
+}
+
+/// 生成随机密码使用的字符集（排除易混淆的 0/O、1/l/I）
+const PASSWORD_CHARSET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz23456789!@#$%^&*-_=+";
+/// 随机密码默认长度
+const DEFAULT_GENERATED_PASSWORD_LENGTH: usize = 16;
+
+/// 用户附加信息（htpasswd 之外的数据）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UserMeta {
+    #[serde(default)]
+    pub groups: Vec<String>,
+}
+
+/// 用户组信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserGroups {
+    /// 用户所属的组
+    pub groups: Vec<String>,
+    /// 配置的包规则中引用的组，供前端选择
+    pub available_groups: Vec<String>,
+}
+
+/// 批量修改密码的单个条目
+#[derive(Debug, Clone, Deserialize)]
+pub struct PasswordUpdate {
+    pub username: String,
+    pub new_password: String,
+}
+
+/// 批量修改密码的单个结果
+#[derive(Debug, Clone, Serialize)]
+pub struct PasswordUpdateResult {
+    pub username: String,
+    pub success: bool,
 }
 
 /// 获取 htpasswd 文件路径
-fn get_htpasswd_path() -> PathBuf {
-    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    home.join(".verdaccio").join("htpasswd")
+///
+/// 优先使用配置中的 `auth.htpasswd.file`（相对路径按配置文件所在目录解析，与 Verdaccio 一致），
+/// 配置未指定时使用默认的 ~/.verdaccio/htpasswd
+pub(crate) fn get_htpasswd_path() -> Result<PathBuf, String> {
+    let configured = load_config().ok().and_then(|config| {
+        config
+            .get("auth")
+            .and_then(|a| a.get("htpasswd"))
+            .and_then(|h| h.get("file"))
+            .and_then(|f| f.as_str())
+            .map(|f| f.trim().to_string())
+            .filter(|f| !f.is_empty())
+    });
+
+    match configured {
+        Some(file) => {
+            let file = PathBuf::from(file);
+            if file.is_absolute() {
+                Ok(file)
+            } else {
+                let config_path = get_config_path()?;
+                let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+                Ok(config_dir.join(file))
+            }
+        }
+        None => Ok(resolve_data_root()?.join(".verdaccio").join("htpasswd")),
+    }
+}
+
+/// 获取用户附加信息文件路径
+pub(crate) fn get_user_meta_path() -> Result<PathBuf, String> {
+    Ok(resolve_data_root()?.join(".mint-verdaccio").join("user-meta.json"))
+}
+
+/// 读取用户附加信息
+fn load_user_meta() -> Result<BTreeMap<String, UserMeta>, String> {
+    let meta_path = get_user_meta_path()?;
+
+    if !meta_path.exists() {
+        return Ok(BTreeMap::new());
+    }
+
+    let content = std::fs::read_to_string(&meta_path)
+        .map_err(|e| format!("读取用户信息文件失败: {}", e))?;
+
+    serde_json::from_str(&content).map_err(|e| format!("解析用户信息文件失败: {}", e))
+}
+
+/// 保存用户附加信息
+fn save_user_meta(meta: &BTreeMap<String, UserMeta>) -> Result<(), String> {
+    let meta_path = get_user_meta_path()?;
+
+    if let Some(parent) = meta_path.parent() {
+        if !parent.exists() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("创建目录失败: {}", e))?;
+        }
+    }
+
+    let content = serde_json::to_string_pretty(meta)
+        .map_err(|e| format!("序列化用户信息失败: {}", e))?;
+
+    atomic_write(&meta_path, content).map_err(|e| format!("保存用户信息文件失败: {}", e))
 }
 
 /// 解析 htpasswd 文件内容
@@ -30,6 +230,27 @@ fn parse_htpasswd(content: &str) -> HashMap<String, String> {
     users
 }
 
+/// 根据哈希前缀判断密码哈希类型（忽略禁用标记）
+fn detect_hash_type(password_hash: &str) -> HashType {
+    let hash = password_hash.trim_start_matches(DISABLED_HASH_PREFIX);
+    if ["$2a$", "$2b$", "$2y$"].iter().any(|p| hash.starts_with(p)) {
+        HashType::Bcrypt
+    } else if hash.starts_with(APR1_MAGIC) {
+        HashType::Apr1
+    } else if hash.starts_with("{SHA}") {
+        HashType::Sha1
+    } else if hash.len() == 13
+        && hash
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '/')
+    {
+        // 传统 DES crypt：2 位 salt + 11 位哈希
+        HashType::Crypt
+    } else {
+        HashType::Unknown
+    }
+}
+
 /// 生成 htpasswd 文件内容
 fn generate_htpasswd(users: &HashMap<String, String>) -> String {
     users
@@ -39,16 +260,163 @@ fn generate_htpasswd(users: &HashMap<String, String>) -> String {
         .join("\n")
 }
 
+/// 读取配置中的 `auth.htpasswd.max_users`（未配置或为 -1 时不限制）
+fn configured_max_users() -> Option<usize> {
+    load_config()
+        .ok()?
+        .get("auth")?
+        .get("htpasswd")?
+        .get("max_users")?
+        .as_i64()
+        .and_then(|max| usize::try_from(max).ok())
+}
+
+/// 验证密码强度
+fn validate_password(password: &str) -> Result<(), String> {
+    if password.is_empty() {
+        return Err("密码不能为空".to_string());
+    }
+    if password.len() < 4 {
+        return Err("密码长度至少为 4 个字符".to_string());
+    }
+    Ok(())
+}
+
+/// 读取配置中的 `auth.htpasswd.rounds`（bcrypt 计算强度，4 到 31），未配置或无效时使用默认值
+fn configured_bcrypt_cost() -> u32 {
+    load_config()
+        .ok()
+        .and_then(|config| config.get("auth")?.get("htpasswd")?.get("rounds")?.as_u64())
+        .and_then(|rounds| u32::try_from(rounds).ok())
+        .filter(|rounds| (4..=31).contains(rounds))
+        .unwrap_or(bcrypt::DEFAULT_COST)
+}
+
 /// 使用 bcrypt 生成密码哈希（Verdaccio 默认使用 bcrypt）
 fn hash_password(password: &str) -> Result<String, String> {
-    bcrypt::hash(password, bcrypt::DEFAULT_COST)
+    bcrypt::hash(password, configured_bcrypt_cost())
         .map_err(|e| format!("密码加密失败: {}", e))
 }
 
+/// 计算 Apache `$apr1$` 格式的 MD5 哈希（salt 最多取前 8 个字符）
+fn apr1_crypt(password: &str, salt: &str) -> String {
+    use md5::{Digest, Md5};
+
+    let password = password.as_bytes();
+    let salt: String = salt.chars().take(8).collect();
+
+    let alternate = Md5::new()
+        .chain_update(password)
+        .chain_update(salt.as_bytes())
+        .chain_update(password)
+        .finalize();
+
+    let mut ctx = Md5::new()
+        .chain_update(password)
+        .chain_update(APR1_MAGIC.as_bytes())
+        .chain_update(salt.as_bytes());
+    for chunk in password.chunks(16) {
+        ctx.update(&alternate[..chunk.len()]);
+    }
+    let mut i = password.len();
+    while i > 0 {
+        if i & 1 == 1 {
+            ctx.update([0u8]);
+        } else {
+            ctx.update(&password[..1]);
+        }
+        i >>= 1;
+    }
+    let mut digest = ctx.finalize();
+
+    for round in 0..1000 {
+        let mut ctx = Md5::new();
+        if round & 1 == 1 {
+            ctx.update(password);
+        } else {
+            ctx.update(digest);
+        }
+        if round % 3 != 0 {
+            ctx.update(salt.as_bytes());
+        }
+        if round % 7 != 0 {
+            ctx.update(password);
+        }
+        if round & 1 == 1 {
+            ctx.update(digest);
+        } else {
+            ctx.update(password);
+        }
+        digest = ctx.finalize();
+    }
+
+    let mut encoded = String::with_capacity(22);
+    let mut push = |mut value: u32, count: usize| {
+        for _ in 0..count {
+            encoded.push(CRYPT_ALPHABET[(value & 0x3f) as usize] as char);
+            value >>= 6;
+        }
+    };
+    for (a, b, c) in [(0, 6, 12), (1, 7, 13), (2, 8, 14), (3, 9, 15), (4, 10, 5)] {
+        push(
+            (u32::from(digest[a]) << 16) | (u32::from(digest[b]) << 8) | u32::from(digest[c]),
+            4,
+        );
+    }
+    push(u32::from(digest[11]), 2);
+
+    format!("{}{}${}", APR1_MAGIC, salt, encoded)
+}
+
+/// 校验密码是否与已有哈希匹配（支持 bcrypt、apr1、SHA1 与传统 DES crypt，忽略禁用标记）
+fn verify_password(password: &str, password_hash: &str) -> Result<bool, String> {
+    let hash = password_hash.trim_start_matches(DISABLED_HASH_PREFIX);
+    match detect_hash_type(hash) {
+        HashType::Bcrypt => {
+            bcrypt::verify(password, hash).map_err(|e| format!("校验密码失败: {}", e))
+        }
+        HashType::Apr1 => {
+            let salt = hash[APR1_MAGIC.len()..].split('$').next().unwrap_or_default();
+            Ok(apr1_crypt(password, salt) == hash)
+        }
+        HashType::Sha1 => {
+            use base64::Engine;
+            use sha1::{Digest, Sha1};
+            let digest = base64::engine::general_purpose::STANDARD.encode(Sha1::digest(password));
+            Ok(hash == format!("{{SHA}}{}", digest))
+        }
+        HashType::Crypt => Ok(des_crypt(password, &hash[..2]).is_some_and(|h| h == hash)),
+        HashType::Unknown => Err("不支持校验该格式的密码哈希".to_string()),
+    }
+}
+
+/// 判断密码哈希是否处于禁用状态
+fn is_disabled_hash(password_hash: &str) -> bool {
+    password_hash.starts_with(DISABLED_HASH_PREFIX)
+}
+
+/// 更新密码时保留用户原有的禁用状态
+fn keep_disabled_state(old_hash: Option<&String>, new_hash: String) -> String {
+    if old_hash.is_some_and(|h| is_disabled_hash(h)) {
+        format!("{}{}", DISABLED_HASH_PREFIX, new_hash)
+    } else {
+        new_hash
+    }
+}
+
+/// 使用系统 CSPRNG 生成随机密码
+fn generate_password(length: usize) -> String {
+    use rand::Rng;
+    let mut rng = rand::rng();
+    (0..length)
+        .map(|_| PASSWORD_CHARSET[rng.random_range(0..PASSWORD_CHARSET.len())] as char)
+        .collect()
+}
+
 /// 获取用户列表
 #[tauri::command]
 pub async fn get_users() -> Result<Vec<UserInfo>, String> {
-    let htpasswd_path = get_htpasswd_path();
+    let htpasswd_path = get_htpasswd_path()?;
     
     if !htpasswd_path.exists() {
         return Ok(vec![]);
@@ -60,17 +428,23 @@ pub async fn get_users() -> Result<Vec<UserInfo>, String> {
     let users = parse_htpasswd(&content);
     
     Ok(users
-        .keys()
-        .map(|username| UserInfo {
+        .iter()
+        .map(|(username, password_hash)| UserInfo {
             username: username.clone(),
             created: None,
+            disabled: is_disabled_hash(password_hash),
+            hash_type: detect_hash_type(password_hash),
         })
         .collect())
 }
 
 /// 添加用户
 #[tauri::command]
-pub async fn add_user(username: String, password: String) -> Result<(), String> {
+pub async fn add_user(
+    lock: State<'_, HtpasswdLock>,
+    username: String,
+    password: String,
+) -> Result<(), String> {
     // 验证用户名
     if username.is_empty() {
         return Err("用户名不能为空".to_string());
@@ -79,15 +453,9 @@ pub async fn add_user(username: String, password: String) -> Result<(), String>
         return Err("用户名包含非法字符".to_string());
     }
     
-    // 验证密码
-    if password.is_empty() {
-        return Err("密码不能为空".to_string());
-    }
-    if password.len() < 4 {
-        return Err("密码长度至少为 4 个字符".to_string());
-    }
+    validate_password(&password)?;
     
-    let htpasswd_path = get_htpasswd_path();
+    let htpasswd_path = get_htpasswd_path()?;
     
     // 确保目录存在
     if let Some(parent) = htpasswd_path.parent() {
@@ -97,43 +465,104 @@ pub async fn add_user(username: String, password: String) -> Result<(), String>
         }
     }
     
+    // 生成密码哈希（bcrypt 较慢，在加锁前完成）
+    let password_hash = hash_password(&password)?;
+
+    insert_user(&lock, &htpasswd_path, &username, password_hash, configured_max_users()).await
+}
+
+/// 加锁后向 htpasswd 文件写入新用户（用户已存在或超出数量上限时拒绝）
+async fn insert_user(
+    lock: &HtpasswdLock,
+    htpasswd_path: &Path,
+    username: &str,
+    password_hash: String,
+    max_users: Option<usize>,
+) -> Result<(), String> {
+    let _guard = lock.acquire(htpasswd_path).await?;
+
     // 读取现有用户
     let mut users = if htpasswd_path.exists() {
-        let content = std::fs::read_to_string(&htpasswd_path)
+        let content = std::fs::read_to_string(htpasswd_path)
             .map_err(|e| format!("读取 htpasswd 文件失败: {}", e))?;
         parse_htpasswd(&content)
     } else {
         HashMap::new()
     };
-    
+
     // 检查用户是否已存在
-    if users.contains_key(&username) {
+    if users.contains_key(username) {
         return Err(format!("用户 {} 已存在", username));
     }
+
+    // 检查是否超出配置的用户数量上限
+    if let Some(max_users) = max_users {
+        if users.len() >= max_users {
+            return Err(format!(
+                "{}: 已达到用户数量上限 {}（auth.htpasswd.max_users）",
+                USER_LIMIT_REACHED_ERROR, max_users
+            ));
+        }
+    }
+
+    users.insert(username.to_string(), password_hash);
+
+    let content = generate_htpasswd(&users);
+    atomic_write(htpasswd_path, content).map_err(|e| format!("写入 htpasswd 文件失败: {}", e))
+}
+
+/// 删除用户
+#[tauri::command]
+pub async fn delete_user(lock: State<'_, HtpasswdLock>, username: String) -> Result<(), String> {
+    let htpasswd_path = get_htpasswd_path()?;
     
-    // 生成密码哈希
-    let password_hash = hash_password(&password)?;
+    if !htpasswd_path.exists() {
+        return Err("htpasswd 文件不存在".to_string());
+    }
+    
+    let _guard = lock.acquire(&htpasswd_path).await?;
+    
+    let content = std::fs::read_to_string(&htpasswd_path)
+        .map_err(|e| format!("读取 htpasswd 文件失败: {}", e))?;
+    
+    let mut users = parse_htpasswd(&content);
     
-    // 添加用户
-    users.insert(username.clone(), password_hash);
+    if !users.contains_key(&username) {
+        return Err(format!("用户 {} 不存在", username));
+    }
+    
+    users.remove(&username);
     
-    // 写入文件
     let content = generate_htpasswd(&users);
-    std::fs::write(&htpasswd_path, content)
+    atomic_write(&htpasswd_path, content)
         .map_err(|e| format!("写入 htpasswd 文件失败: {}", e))?;
     
+    // 同步清理用户附加信息
+    let mut meta = load_user_meta()?;
+    if meta.remove(&username).is_some() {
+        save_user_meta(&meta)?;
+    }
+    
     Ok(())
 }
 
-/// 删除用户
+/// 修改用户密码
 #[tauri::command]
-pub async fn delete_user(username: String) -> Result<(), String> {
-    let htpasswd_path = get_htpasswd_path();
+pub async fn change_user_password(
+    lock: State<'_, HtpasswdLock>,
+    username: String,
+    new_password: String,
+) -> Result<(), String> {
+    validate_password(&new_password)?;
+    
+    let htpasswd_path = get_htpasswd_path()?;
     
     if !htpasswd_path.exists() {
         return Err("htpasswd 文件不存在".to_string());
     }
     
+    let _guard = lock.acquire(&htpasswd_path).await?;
+    
     let content = std::fs::read_to_string(&htpasswd_path)
         .map_err(|e| format!("读取 htpasswd 文件失败: {}", e))?;
     
@@ -143,32 +572,126 @@ pub async fn delete_user(username: String) -> Result<(), String> {
         return Err(format!("用户 {} 不存在", username));
     }
     
-    users.remove(&username);
+    // 生成新密码哈希
+    let password_hash = hash_password(&new_password)?;
+    
+    let password_hash = keep_disabled_state(users.get(&username), password_hash);
+    users.insert(username, password_hash);
     
     let content = generate_htpasswd(&users);
-    std::fs::write(&htpasswd_path, content)
+    atomic_write(&htpasswd_path, content)
         .map_err(|e| format!("写入 htpasswd 文件失败: {}", e))?;
     
     Ok(())
 }
 
-/// 修改用户密码
+/// 批量修改密码：先校验全部条目（任何用户不存在或密码过弱都不会修改），
+/// 再在一次加锁的读写中应用所有修改，避免只轮换了部分用户
 #[tauri::command]
-pub async fn change_user_password(username: String, new_password: String) -> Result<(), String> {
-    // 验证密码
-    if new_password.is_empty() {
-        return Err("密码不能为空".to_string());
+pub async fn change_passwords(
+    lock: State<'_, HtpasswdLock>,
+    updates: Vec<PasswordUpdate>,
+) -> Result<Vec<PasswordUpdateResult>, String> {
+    for update in &updates {
+        validate_password(&update.new_password)
+            .map_err(|e| format!("用户 {}: {}", update.username, e))?;
     }
-    if new_password.len() < 4 {
-        return Err("密码长度至少为 4 个字符".to_string());
+
+    let htpasswd_path = get_htpasswd_path()?;
+    
+    if !htpasswd_path.exists() {
+        return Err("htpasswd 文件不存在".to_string());
+    }
+    
+    let _guard = lock.acquire(&htpasswd_path).await?;
+    
+    let content = std::fs::read_to_string(&htpasswd_path)
+        .map_err(|e| format!("读取 htpasswd 文件失败: {}", e))?;
+    
+    let mut users = parse_htpasswd(&content);
+    
+    if let Some(missing) = updates.iter().find(|u| !users.contains_key(&u.username)) {
+        return Err(format!("用户 {} 不存在", missing.username));
+    }
+    
+    let mut results = Vec::with_capacity(updates.len());
+    for update in updates {
+        let password_hash = hash_password(&update.new_password)?;
+        let password_hash = keep_disabled_state(users.get(&update.username), password_hash);
+        users.insert(update.username.clone(), password_hash);
+        results.push(PasswordUpdateResult {
+            username: update.username,
+            success: true,
+        });
     }
     
-    let htpasswd_path = get_htpasswd_path();
+    let content = generate_htpasswd(&users);
+    atomic_write(&htpasswd_path, content)
+        .map_err(|e| format!("写入 htpasswd 文件失败: {}", e))?;
+    
+    Ok(results)
+}
+
+/// 将旧格式（apr1 / SHA1 等）的用户哈希重新生成为 bcrypt（使用配置的 `rounds`）
+///
+/// 需提供当前密码，校验通过后才替换哈希，密码本身不变；禁用状态保持不变
+#[tauri::command]
+pub async fn rehash_user(
+    lock: State<'_, HtpasswdLock>,
+    username: String,
+    password: String,
+) -> Result<(), String> {
+    let htpasswd_path = get_htpasswd_path()?;
     
     if !htpasswd_path.exists() {
         return Err("htpasswd 文件不存在".to_string());
     }
     
+    let _guard = lock.acquire(&htpasswd_path).await?;
+    
+    let content = std::fs::read_to_string(&htpasswd_path)
+        .map_err(|e| format!("读取 htpasswd 文件失败: {}", e))?;
+    
+    let mut users = parse_htpasswd(&content);
+    
+    let old_hash = users
+        .get(&username)
+        .ok_or_else(|| format!("用户 {} 不存在", username))?;
+    if !verify_password(&password, old_hash)? {
+        return Err("密码与现有哈希不匹配".to_string());
+    }
+    
+    let password_hash = hash_password(&password)?;
+    let password_hash = keep_disabled_state(Some(old_hash), password_hash);
+    users.insert(username, password_hash);
+    
+    let content = generate_htpasswd(&users);
+    atomic_write(&htpasswd_path, content)
+        .map_err(|e| format!("写入 htpasswd 文件失败: {}", e))?;
+    
+    Ok(())
+}
+
+/// 重置用户密码为随机生成的强密码，并返回明文（仅此一次）
+#[tauri::command]
+pub async fn reset_user_password(
+    lock: State<'_, HtpasswdLock>,
+    username: String,
+    length: Option<usize>,
+) -> Result<String, String> {
+    let length = length.unwrap_or(DEFAULT_GENERATED_PASSWORD_LENGTH);
+    if !(8..=128).contains(&length) {
+        return Err("密码长度需在 8 到 128 之间".to_string());
+    }
+    
+    let htpasswd_path = get_htpasswd_path()?;
+    
+    if !htpasswd_path.exists() {
+        return Err("htpasswd 文件不存在".to_string());
+    }
+    
+    let _guard = lock.acquire(&htpasswd_path).await?;
+    
     let content = std::fs::read_to_string(&htpasswd_path)
         .map_err(|e| format!("读取 htpasswd 文件失败: {}", e))?;
     
@@ -178,13 +701,57 @@ pub async fn change_user_password(username: String, new_password: String) -> Res
         return Err(format!("用户 {} 不存在", username));
     }
     
-    // 生成新密码哈希
-    let password_hash = hash_password(&new_password)?;
+    let password = generate_password(length);
+    let password_hash = hash_password(&password)?;
     
+    let password_hash = keep_disabled_state(users.get(&username), password_hash);
     users.insert(username, password_hash);
     
     let content = generate_htpasswd(&users);
-    std::fs::write(&htpasswd_path, content)
+    atomic_write(&htpasswd_path, content)
+        .map_err(|e| format!("写入 htpasswd 文件失败: {}", e))?;
+    
+    Ok(password)
+}
+
+/// 禁用或启用用户
+///
+/// 禁用时在密码哈希前添加 `!` 标记使其无法通过认证，启用时移除标记恢复原密码
+#[tauri::command]
+pub async fn set_user_disabled(
+    lock: State<'_, HtpasswdLock>,
+    username: String,
+    disabled: bool,
+) -> Result<(), String> {
+    let htpasswd_path = get_htpasswd_path()?;
+    
+    if !htpasswd_path.exists() {
+        return Err("htpasswd 文件不存在".to_string());
+    }
+    
+    let _guard = lock.acquire(&htpasswd_path).await?;
+    
+    let content = std::fs::read_to_string(&htpasswd_path)
+        .map_err(|e| format!("读取 htpasswd 文件失败: {}", e))?;
+    
+    let mut users = parse_htpasswd(&content);
+    
+    let Some(password_hash) = users.get_mut(&username) else {
+        return Err(format!("用户 {} 不存在", username));
+    };
+    
+    if is_disabled_hash(password_hash) == disabled {
+        return Ok(());
+    }
+    
+    if disabled {
+        password_hash.insert(0, DISABLED_HASH_PREFIX);
+    } else {
+        password_hash.remove(0);
+    }
+    
+    let content = generate_htpasswd(&users);
+    atomic_write(&htpasswd_path, content)
         .map_err(|e| format!("写入 htpasswd 文件失败: {}", e))?;
     
     Ok(())
@@ -196,3 +763,241 @@ pub async fn get_user_count() -> Result<usize, String> {
     let users = get_users().await?;
     Ok(users.len())
 }
+
+/// 获取用户所属的组，以及配置中可用的组
+#[tauri::command]
+pub async fn get_user_groups(username: String) -> Result<UserGroups, String> {
+    let meta = load_user_meta()?;
+    let groups = meta
+        .get(&username)
+        .map(|m| m.groups.clone())
+        .unwrap_or_default();
+
+    // 配置文件缺失或无法解析时不影响读取用户组
+    let available_groups = load_config()
+        .map(|config| configured_groups(&config))
+        .unwrap_or_default();
+
+    Ok(UserGroups {
+        groups,
+        available_groups,
+    })
+}
+
+/// 设置用户所属的组
+///
+/// 组信息保存在 user-meta.json 中，不会写入 htpasswd。Verdaccio 自带的 htpasswd 认证插件
+/// 是否支持按组授权取决于其版本，若不支持，基于组名的包规则需要配合其他认证插件使用
+#[tauri::command]
+pub async fn set_user_groups(username: String, groups: Vec<String>) -> Result<(), String> {
+    let htpasswd_path = get_htpasswd_path()?;
+    let users = if htpasswd_path.exists() {
+        let content = std::fs::read_to_string(&htpasswd_path)
+            .map_err(|e| format!("读取 htpasswd 文件失败: {}", e))?;
+        parse_htpasswd(&content)
+    } else {
+        HashMap::new()
+    };
+
+    if !users.contains_key(&username) {
+        return Err(format!("用户 {} 不存在", username));
+    }
+
+    let mut groups: Vec<String> = groups
+        .into_iter()
+        .map(|g| g.trim().to_string())
+        .filter(|g| !g.is_empty())
+        .collect();
+    if let Some(invalid) = groups.iter().find(|g| !is_valid_group_name(g)) {
+        return Err(format!("组名 {} 包含非法字符", invalid));
+    }
+    groups.sort();
+    groups.dedup();
+
+    let mut meta = load_user_meta()?;
+    if groups.is_empty() {
+        meta.remove(&username);
+    } else {
+        meta.entry(username).or_default().groups = groups;
+    }
+
+    save_user_meta(&meta)
+}
+
+/// 清空用户数据库：备份并删除 htpasswd 文件，同时删除用户附加信息，返回被删除的用户数量
+///
+/// 仅影响用户，包与配置保持不变。需传入 [`RESET_USERS_CONFIRMATION`] 作为确认
+#[tauri::command]
+pub async fn reset_users(
+    lock: State<'_, HtpasswdLock>,
+    confirmation: String,
+) -> Result<usize, String> {
+    if confirmation != RESET_USERS_CONFIRMATION {
+        return Err(format!("请输入 {} 以确认清空所有用户", RESET_USERS_CONFIRMATION));
+    }
+
+    let htpasswd_path = get_htpasswd_path()?;
+    let mut removed = 0;
+
+    if htpasswd_path.exists() {
+        let _guard = lock.acquire(&htpasswd_path).await?;
+
+        let content = std::fs::read_to_string(&htpasswd_path)
+            .map_err(|e| format!("读取 htpasswd 文件失败: {}", e))?;
+        removed = parse_htpasswd(&content).len();
+
+        let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+        let backup_path = htpasswd_path.with_extension(format!("{}.bak", timestamp));
+        std::fs::copy(&htpasswd_path, &backup_path)
+            .map_err(|e| format!("备份 htpasswd 文件失败: {}", e))?;
+        std::fs::remove_file(&htpasswd_path)
+            .map_err(|e| format!("删除 htpasswd 文件失败: {}", e))?;
+    }
+
+    let meta_path = get_user_meta_path()?;
+    if meta_path.exists() {
+        std::fs::remove_file(&meta_path).map_err(|e| format!("删除用户信息文件失败: {}", e))?;
+    }
+
+    Ok(removed)
+}
+
+/// 登录测试结果
+#[derive(Debug, Clone, Serialize)]
+pub struct LoginTestResult {
+    pub authenticated: bool,
+    pub token: Option<String>,
+    pub message: Option<String>,
+}
+
+/// 向运行中的服务发起与 `npm login` 相同的登录请求，端到端验证用户凭据
+///
+/// 与本地校验密码哈希不同，该请求经过 Verdaccio 实际的认证流程，可发现配置层面的认证问题。
+/// 请求同时携带 Basic 认证，使 Verdaccio 按已有用户登录处理，而不会尝试注册新用户
+#[tauri::command]
+pub async fn test_login(
+    port: u16,
+    username: String,
+    password: String,
+) -> Result<LoginTestResult, String> {
+    let timeout_ms = load_app_settings()?.api_timeout_ms;
+    let client = http_client_builder()?
+        .timeout(Duration::from_millis(timeout_ms))
+        .build()
+        .map_err(|e| format!("创建 HTTP 客户端失败: {}", e))?;
+
+    let url = format!("http://localhost:{}/-/user/org.couchdb.user:{}", port, username);
+    let response = client
+        .put(&url)
+        .basic_auth(&username, Some(&password))
+        .json(&serde_json::json!({
+            "_id": format!("org.couchdb.user:{}", username),
+            "name": username,
+            "password": password,
+            "type": "user",
+            "roles": [],
+            "date": chrono::Utc::now().to_rfc3339(),
+        }))
+        .send()
+        .await
+        .map_err(|e| format!("请求失败: {}", e))?;
+
+    let status = response.status();
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+    let token = body.get("token").and_then(|t| t.as_str()).map(String::from);
+    let message = body
+        .get("error")
+        .or_else(|| body.get("ok"))
+        .and_then(|m| m.as_str())
+        .map(String::from);
+
+    Ok(LoginTestResult {
+        authenticated: status.is_success() && token.is_some(),
+        token,
+        message: message.or_else(|| Some(status.to_string())),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_add_user_keeps_every_user() {
+        let dir = tempfile::tempdir().unwrap();
+        let htpasswd_path = dir.path().join("htpasswd");
+        let lock = std::sync::Arc::new(HtpasswdLock::default());
+
+        let tasks: Vec<_> = (0..16)
+            .map(|i| {
+                let lock = lock.clone();
+                let htpasswd_path = htpasswd_path.clone();
+                tokio::spawn(async move {
+                    let hash = format!("{{SHA}}hash{}", i);
+                    insert_user(&lock, &htpasswd_path, &format!("user{}", i), hash, None).await
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+
+        let content = std::fs::read_to_string(&htpasswd_path).unwrap();
+        let users = parse_htpasswd(&content);
+        assert_eq!(users.len(), 16);
+        assert!((0..16).all(|i| users.contains_key(&format!("user{}", i))));
+        assert!(!dir.path().join("htpasswd.lock").exists());
+    }
+
+    #[tokio::test]
+    async fn add_user_respects_max_users() {
+        let dir = tempfile::tempdir().unwrap();
+        let htpasswd_path = dir.path().join("htpasswd");
+        let lock = HtpasswdLock::default();
+
+        insert_user(&lock, &htpasswd_path, "alice", "{SHA}a".to_string(), Some(1))
+            .await
+            .unwrap();
+        let error = insert_user(&lock, &htpasswd_path, "bob", "{SHA}b".to_string(), Some(1))
+            .await
+            .unwrap_err();
+        assert!(error.starts_with(USER_LIMIT_REACHED_ERROR));
+    }
+
+    #[test]
+    fn apr1_crypt_matches_htpasswd_vectors() {
+        assert_eq!(apr1_crypt("password", "abcdefgh"), "$apr1$abcdefgh$FBwExRW4dCc8aL.OvjpIE1");
+        assert_eq!(apr1_crypt("myPassword", "rOs/5QMn"), "$apr1$rOs/5QMn$dTu56ZjDJQavyAEQew0b./");
+        // salt 不足 8 个字符
+        assert_eq!(apr1_crypt("secret", "xyz"), "$apr1$xyz$HXgo9gtz4gpj4JWTLYmjB0");
+        // salt 超过 8 个字符时只取前 8 个
+        assert_eq!(apr1_crypt("password", "abcdefghij"), "$apr1$abcdefgh$FBwExRW4dCc8aL.OvjpIE1");
+    }
+
+    #[test]
+    fn verify_password_supports_apr1_and_sha1() {
+        assert!(verify_password("secret", "$apr1$xyz$HXgo9gtz4gpj4JWTLYmjB0").unwrap());
+        assert!(!verify_password("wrong", "$apr1$xyz$HXgo9gtz4gpj4JWTLYmjB0").unwrap());
+
+        assert!(verify_password("password", "{SHA}W6ph5Mm5Pz8GgiULbPgzG37mj9g=").unwrap());
+        assert!(!verify_password("wrong", "{SHA}W6ph5Mm5Pz8GgiULbPgzG37mj9g=").unwrap());
+
+        assert!(verify_password("password", "abJnggxhB/yWI").unwrap());
+    }
+
+    #[test]
+    fn verify_password_round_trips_bcrypt() {
+        let hash = bcrypt::hash("password", 4).unwrap();
+        assert!(verify_password("password", &hash).unwrap());
+        assert!(!verify_password("wrong", &hash).unwrap());
+    }
+
+    #[test]
+    fn verify_password_ignores_disabled_marker() {
+        let hash = format!("{}{}", DISABLED_HASH_PREFIX, "{SHA}W6ph5Mm5Pz8GgiULbPgzG37mj9g=");
+        assert!(is_disabled_hash(&hash));
+        assert_eq!(detect_hash_type(&hash), HashType::Sha1);
+        assert!(verify_password("password", &hash).unwrap());
+        assert!(!verify_password("wrong", &hash).unwrap());
+    }
+}
@@ -1,10 +1,16 @@
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
-use std::path::PathBuf;
-use std::sync::Mutex;
-use tauri::{AppHandle, Manager, State};
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use tauri::{AppHandle, Emitter, Manager, State};
 use tauri_plugin_shell::{process::CommandChild, ShellExt};
 
+use super::config::{backup_config, config_hash, load_config, write_config_file};
+use super::fsutil::resolve_data_root;
+use super::settings::{load_app_settings, write_app_settings};
+use super::users::get_htpasswd_path;
+
 /// 日志条目
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogEntry {
@@ -13,6 +19,16 @@ pub struct LogEntry {
     pub message: String,
 }
 
+/// 从 http 日志解析出的请求记录
+#[derive(Debug, Clone, Serialize)]
+pub struct RequestEntry {
+    pub method: String,
+    pub path: String,
+    pub status: u16,
+    pub remote_addr: Option<String>,
+    pub time: String,
+}
+
 /// Verdaccio 运行状态
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -32,6 +48,51 @@ pub struct VerdaccioStatus {
     pub config_path: String,
 }
 
+/// 首次运行环境检查结果
+#[derive(Debug, Clone, Serialize)]
+pub struct SetupStatus {
+    pub node_sidecar: bool,
+    pub verdaccio_entry: bool,
+    pub config_exists: bool,
+    pub storage_exists: bool,
+    pub htpasswd_exists: bool,
+}
+
+/// 内置与实际运行的 Verdaccio 版本
+#[derive(Debug, Clone, Serialize)]
+pub struct VerdaccioVersions {
+    pub bundled: Option<String>,
+    pub running: Option<String>,
+    /// 两个版本都能获取且不一致
+    pub mismatch: bool,
+}
+
+/// 外部 Verdaccio 检测结果
+#[derive(Debug, Clone, Serialize)]
+pub struct ExternalVerdaccio {
+    /// 端口上有不是由本应用启动的 Verdaccio 在响应
+    pub external: bool,
+    /// 端口是否已被占用（可能是 Verdaccio，也可能是其他程序）
+    pub port_in_use: bool,
+}
+
+/// Verdaccio 进程资源占用
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessStats {
+    /// CPU 占用（100 表示占满一个核心）
+    pub cpu_percent: f32,
+    pub memory_bytes: u64,
+}
+
+/// 热重载配置的结果
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigReloadResult {
+    /// 是否在超时前观察到配置重新加载的日志
+    pub reloaded: bool,
+    /// 当前平台或 Verdaccio 版本不支持热重载，需要重启服务才能应用配置
+    pub restart_required: bool,
+}
+
 /// 全局 Verdaccio 进程管理器
 pub struct VerdaccioProcess {
     pub child: Mutex<Option<CommandChild>>,
@@ -39,10 +100,57 @@ pub struct VerdaccioProcess {
     pub pid: Mutex<Option<u32>>,
     pub logs: Mutex<VecDeque<LogEntry>>,
     pub is_running: Mutex<bool>,
+    /// 是否记录进程输出（暂停时仍保留 stderr 及 error / fatal 级别的日志）
+    pub log_capture: AtomicBool,
+    /// 是否已接管外部启动的 Verdaccio（此时没有子进程，不能由本应用停止）
+    pub attached: AtomicBool,
+    /// 等待批量推送给前端的日志
+    pending_logs: Mutex<Vec<LogEntry>>,
+    /// Node.js sidecar 版本缓存
+    node_version: Mutex<Option<String>>,
 }
 
 const MAX_LOG_ENTRIES: usize = 1000;
 
+/// 检查接管的外部 Verdaccio 是否仍可连接的超时时间
+const ATTACHED_PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// 启动时记录环境变量的日志前缀（诊断包据此隐藏环境变量值）
+pub(crate) const ENV_LOG_PREFIX: &str = "环境变量: ";
+
+/// 日志条目 `timestamp` 的格式
+const LOG_TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f";
+
+/// 日志批量推送的合并窗口
+const LOG_BATCH_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// 详细日志模式下注入的环境变量（用户在设置中配置的同名变量优先）
+const VERBOSE_ENV: &[(&str, &str)] = &[("NODE_DEBUG", "http,net"), ("DEBUG", "verdaccio*")];
+
+/// 发送 SIGHUP 后等待配置重新加载日志的时间
+const CONFIG_RELOAD_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// 能处理 SIGHUP 并重新加载配置的最低 Verdaccio 版本
+///
+/// 目前的 Verdaccio 没有注册 SIGHUP 处理函数，收到信号后会按 Node 默认行为直接退出，
+/// 因此为 None，始终提示重启；确认某个版本支持后再填写
+const SIGHUP_RELOAD_MIN_VERSION: Option<&str> = None;
+
+/// 支持热重载的 Verdaccio 重新加载配置后输出的日志内容
+const CONFIG_RELOADED_LOG: &str = "config file reloaded";
+
+/// 常见 Verdaccio 错误的匹配规则：(输出中的特征字符串, 给用户的说明)
+const KNOWN_ERROR_PATTERNS: &[(&str, &str)] = &[
+    ("EADDRINUSE", "端口已被占用，请更换端口或关闭占用该端口的程序"),
+    ("EACCES", "没有权限访问端口或文件，请检查端口号（1024 以下需要管理员权限）或目录权限"),
+    ("EADDRNOTAVAIL", "监听地址不可用，请检查监听地址是否属于本机网卡"),
+    ("config error", "配置文件有误，请检查 config.yaml"),
+    ("YAMLException", "配置文件 YAML 格式错误，请检查 config.yaml"),
+    ("ENOSPC", "磁盘空间不足"),
+    ("heap out of memory", "Node.js 内存不足，可在设置中添加 --max-old-space-size 参数"),
+    ("plugin not found", "配置中引用的插件不存在，请检查插件是否已安装"),
+];
+
 impl Default for VerdaccioProcess {
     fn default() -> Self {
         Self {
@@ -51,6 +159,10 @@ impl Default for VerdaccioProcess {
             pid: Mutex::new(None),
             logs: Mutex::new(VecDeque::with_capacity(MAX_LOG_ENTRIES)),
             is_running: Mutex::new(false),
+            log_capture: AtomicBool::new(true),
+            attached: AtomicBool::new(false),
+            pending_logs: Mutex::new(Vec::new()),
+            node_version: Mutex::new(None),
         }
     }
 }
@@ -58,51 +170,100 @@ impl Default for VerdaccioProcess {
 impl VerdaccioProcess {
     /// 移除 ANSI 转义序列（颜色代码）
     fn strip_ansi_codes(s: &str) -> String {
-        let re = regex::Regex::new(r"\x1b\[[0-9;]*m").unwrap();
-        re.replace_all(s, "").to_string()
+        static ANSI_RE: LazyLock<regex::Regex> =
+            LazyLock::new(|| regex::Regex::new(r"\x1b\[[0-9;]*m").unwrap());
+        ANSI_RE.replace_all(s, "").to_string()
     }
 
     pub fn add_log(&self, level: &str, message: String) {
         if let Ok(mut logs) = self.logs.lock() {
-            let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string();
+            let timestamp = chrono::Local::now().format(LOG_TIMESTAMP_FORMAT).to_string();
             // 移除 ANSI 颜色代码
             let clean_message = Self::strip_ansi_codes(&message);
-            logs.push_back(LogEntry {
+            let entry = LogEntry {
                 timestamp,
                 level: level.to_string(),
                 message: clean_message,
-            });
+            };
+            if let Ok(mut pending) = self.pending_logs.lock() {
+                if pending.len() < MAX_LOG_ENTRIES {
+                    pending.push(entry.clone());
+                }
+            }
+            logs.push_back(entry);
             while logs.len() > MAX_LOG_ENTRIES {
                 logs.pop_front();
             }
         }
     }
 
+    /// 匹配输出中的已知错误，命中时追加一条说明性的 ERROR 日志（原始输出仍会保留）
+    fn add_known_error_hint(&self, output: &str) {
+        if let Some((_, hint)) = KNOWN_ERROR_PATTERNS
+            .iter()
+            .find(|(pattern, _)| output.contains(pattern))
+        {
+            self.add_log("ERROR", hint.to_string());
+        }
+    }
+
     pub fn set_running(&self, running: bool) {
         if let Ok(mut is_running) = self.is_running.lock() {
             *is_running = running;
         }
     }
 
+    /// 清除进程相关状态（调用方需持有 child 锁，保证与进程退出回调的清理顺序一致）
+    fn clear_process_state(&self) {
+        if let Ok(mut pid) = self.pid.lock() {
+            *pid = None;
+        }
+        self.set_running(false);
+    }
+
     pub fn check_running(&self) -> bool {
         self.is_running.lock().map(|r| *r).unwrap_or(false)
     }
+
+    /// 接管外部 Verdaccio 时检查其端口是否仍可连接，外部进程退出后解除接管并清除运行状态
+    ///
+    /// 接管模式下没有子进程退出回调，需由状态轮询调用
+    pub async fn refresh_attached(&self) {
+        if !self.attached.load(Ordering::Relaxed) {
+            return;
+        }
+        let Ok(port) = self.port.lock().map(|port| *port) else {
+            return;
+        };
+
+        let connect = tokio::net::TcpStream::connect(("localhost", port));
+        let reachable = tokio::time::timeout(ATTACHED_PROBE_TIMEOUT, connect)
+            .await
+            .is_ok_and(|result| result.is_ok());
+
+        if !reachable && self.attached.swap(false, Ordering::Relaxed) {
+            self.set_running(false);
+            self.add_log(
+                "WARN",
+                format!("端口 {} 上的外部 Verdaccio 已无法连接，已断开接管", port),
+            );
+        }
+    }
 }
 
 /// 获取 Verdaccio 配置目录
-fn get_verdaccio_dir() -> PathBuf {
-    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-    home.join(".verdaccio")
+fn get_verdaccio_dir() -> Result<PathBuf, String> {
+    Ok(resolve_data_root()?.join(".verdaccio"))
 }
 
 /// 获取 Verdaccio 配置文件路径
-fn get_config_path() -> PathBuf {
-    get_verdaccio_dir().join("config.yaml")
+pub(crate) fn get_config_path() -> Result<PathBuf, String> {
+    Ok(get_verdaccio_dir()?.join("config.yaml"))
 }
 
 /// 获取 Verdaccio 存储目录
-fn get_storage_path() -> PathBuf {
-    get_verdaccio_dir().join("storage")
+fn get_storage_path() -> Result<PathBuf, String> {
+    Ok(get_verdaccio_dir()?.join("storage"))
 }
 
 /// 获取 Verdaccio 入口文件路径（从资源目录）
@@ -165,8 +326,8 @@ fn get_verdaccio_entry(app: &AppHandle) -> Result<PathBuf, String> {
 
 /// 初始化 Verdaccio 配置目录
 fn ensure_verdaccio_dirs() -> Result<(), String> {
-    let verdaccio_dir = get_verdaccio_dir();
-    let storage_dir = get_storage_path();
+    let verdaccio_dir = get_verdaccio_dir()?;
+    let storage_dir = get_storage_path()?;
 
     if !verdaccio_dir.exists() {
         std::fs::create_dir_all(&verdaccio_dir)
@@ -178,7 +339,7 @@ fn ensure_verdaccio_dirs() -> Result<(), String> {
             .map_err(|e| format!("创建存储目录失败: {}", e))?;
     }
 
-    let config_path = get_config_path();
+    let config_path = get_config_path()?;
     if !config_path.exists() {
         let default_config = r#"# Verdaccio 配置文件
 storage: ./storage
@@ -209,13 +370,89 @@ log:
   format: pretty
   level: http
 "#;
-        std::fs::write(&config_path, default_config)
+        write_config_file(&config_path, default_config)
             .map_err(|e| format!("创建配置文件失败: {}", e))?;
     }
 
     Ok(())
 }
 
+/// 解析监听地址：指定了 `listen_address` 时优先使用（需为合法 IP），否则根据 allow_lan 选择
+fn resolve_listen_ip(allow_lan: bool, listen_address: Option<&str>) -> Result<std::net::IpAddr, String> {
+    match listen_address.map(str::trim).filter(|a| !a.is_empty()) {
+        Some(address) => address
+            .parse()
+            .map_err(|_| format!("无效的监听地址: {}", address)),
+        None if allow_lan => Ok(std::net::Ipv4Addr::UNSPECIFIED.into()),
+        None => Ok(std::net::Ipv4Addr::LOCALHOST.into()),
+    }
+}
+
+/// 校验额外的 Node.js 参数（只允许 `--` 开头的选项）
+pub(crate) fn validate_node_args(node_args: &[String]) -> Result<(), String> {
+    match node_args.iter().find(|arg| !arg.starts_with("--")) {
+        Some(arg) => Err(format!("无效的 Node.js 参数: {}，参数必须以 -- 开头", arg)),
+        None => Ok(()),
+    }
+}
+
+/// 判断环境变量名是否像是密钥（日志中需隐藏其值）
+pub(crate) fn is_secret_env_key(key: &str) -> bool {
+    let key = key.to_uppercase();
+    ["SECRET", "TOKEN", "PASSWORD", "PASSWD", "KEY", "AUTH", "CREDENTIAL"]
+        .iter()
+        .any(|marker| key.contains(marker))
+}
+
+/// 校验环境变量名（非空，且不包含 `=` 或空字符）
+pub(crate) fn validate_env(env: &HashMap<String, String>) -> Result<(), String> {
+    match env
+        .keys()
+        .find(|key| key.is_empty() || key.contains(['=', '\0']))
+    {
+        Some(key) => Err(format!("无效的环境变量名: {:?}", key)),
+        None => Ok(()),
+    }
+}
+
+/// 构建传给 Node.js 的启动参数（额外的 Node.js 参数位于 Verdaccio 入口之前）
+fn launch_args(
+    node_args: &[String],
+    verdaccio_entry: &Path,
+    config_path: &Path,
+    listen_ip: std::net::IpAddr,
+    port: u16,
+) -> Vec<String> {
+    let mut args = node_args.to_vec();
+    args.extend([
+        verdaccio_entry.to_string_lossy().to_string(),
+        "--config".to_string(),
+        config_path.to_string_lossy().to_string(),
+        "--listen".to_string(),
+        listen_value(listen_ip, port),
+    ]);
+    args
+}
+
+/// 生成 `--listen` 参数值（IPv6 地址会加上方括号）
+fn listen_value(listen_ip: std::net::IpAddr, port: u16) -> String {
+    std::net::SocketAddr::new(listen_ip, port).to_string()
+}
+
+/// 预览按当前输入启动时使用的 `--listen` 值，与 [`start_verdaccio`] 的解析规则一致
+///
+/// `listen_address` 非空时优先使用（需为合法 IP），否则 `allow_lan` 为 true 时监听 `0.0.0.0`，
+/// 为 false 时仅监听 `127.0.0.1`
+#[tauri::command]
+pub async fn preview_listen(
+    port: u16,
+    allow_lan: bool,
+    listen_address: Option<String>,
+) -> Result<String, String> {
+    let listen_ip = resolve_listen_ip(allow_lan, listen_address.as_deref())?;
+    Ok(listen_value(listen_ip, port))
+}
+
 /// 启动 Verdaccio 服务（使用 Node.js sidecar + Verdaccio 资源）
 #[tauri::command]
 pub async fn start_verdaccio(
@@ -223,7 +460,14 @@ pub async fn start_verdaccio(
     process: State<'_, VerdaccioProcess>,
     port: u16,
     allow_lan: bool,
+    listen_address: Option<String>,
 ) -> Result<VerdaccioStatus, String> {
+    // 在创建目录和启动进程前校验监听地址与 Node.js 参数
+    let listen_ip = resolve_listen_ip(allow_lan, listen_address.as_deref())?;
+    let settings = load_app_settings()?;
+    validate_node_args(&settings.node_args)?;
+    validate_env(&settings.env)?;
+
     ensure_verdaccio_dirs()?;
 
     if process.check_running() {
@@ -237,7 +481,7 @@ pub async fn start_verdaccio(
         }
     }
 
-    let config_path = get_config_path();
+    let config_path = get_config_path()?;
     let verdaccio_entry = get_verdaccio_entry(&app)?;
 
     process.add_log("INFO", format!("正在启动 Verdaccio..."));
@@ -245,9 +489,24 @@ pub async fn start_verdaccio(
     process.add_log("INFO", format!("配置文件: {}", config_path.display()));
     process.add_log("INFO", format!("监听端口: {}", port));
 
-    // 根据 allow_lan 设置监听地址
-    let listen_host = if allow_lan { "0.0.0.0" } else { "127.0.0.1" };
-    process.add_log("INFO", format!("监听地址: {}", listen_host));
+    process.add_log("INFO", format!("监听地址: {}", listen_ip));
+
+    let args = launch_args(&settings.node_args, &verdaccio_entry, &config_path, listen_ip, port);
+    process.add_log("INFO", format!("启动参数: {}", args.join(" ")));
+
+    let mut env: HashMap<String, String> = HashMap::new();
+    if settings.verbose_mode {
+        process.add_log("INFO", "详细日志模式已开启".to_string());
+        env.extend(VERBOSE_ENV.iter().map(|(k, v)| (k.to_string(), v.to_string())));
+    }
+    env.extend(settings.env.clone());
+
+    let mut env_keys: Vec<&String> = env.keys().collect();
+    env_keys.sort();
+    for key in env_keys {
+        let value = if is_secret_env_key(key) { "******" } else { env[key].as_str() };
+        process.add_log("INFO", format!("{}{}={}", ENV_LOG_PREFIX, key, value));
+    }
 
     // 使用 Node.js sidecar 运行 Verdaccio
     let sidecar = app
@@ -258,13 +517,8 @@ pub async fn start_verdaccio(
             process.add_log("ERROR", msg.clone());
             msg
         })?
-        .args([
-            verdaccio_entry.to_str().unwrap(),
-            "--config",
-            config_path.to_str().unwrap(),
-            "--listen",
-            &format!("{}:{}", listen_host, port),
-        ]);
+        .args(args)
+        .envs(env);
 
     let (mut rx, child) = sidecar.spawn().map_err(|e| {
         let msg = format!("启动 Verdaccio 失败: {}", e);
@@ -286,6 +540,25 @@ pub async fn start_verdaccio(
 
     process.set_running(true);
 
+    // 记住本次使用的端口、局域网设置与监听地址，应用重启后状态（及二维码地址）仍能反映实际配置
+    let listen_address = listen_address
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty());
+    if settings.default_port != port
+        || settings.allow_lan != allow_lan
+        || settings.listen_address != listen_address
+    {
+        let saved = load_app_settings().and_then(|mut latest| {
+            latest.default_port = port;
+            latest.allow_lan = allow_lan;
+            latest.listen_address = listen_address;
+            write_app_settings(&latest)
+        });
+        if let Err(e) = saved {
+            process.add_log("WARN", format!("保存端口设置失败: {}", e));
+        }
+    }
+
     let app_handle = app.clone();
 
     tauri::async_runtime::spawn(async move {
@@ -297,13 +570,20 @@ pub async fn start_verdaccio(
                     CommandEvent::Stdout(line) => {
                         let output = String::from_utf8_lossy(&line).trim().to_string();
                         if !output.is_empty() {
-                            process_state.add_log("STDOUT", output);
+                            if process_state.log_capture.load(Ordering::Relaxed)
+                                || is_error_log_line(&output)
+                            {
+                                process_state.add_log("STDOUT", output.clone());
+                            }
+                            process_state.add_known_error_hint(&output);
                         }
                     }
                     CommandEvent::Stderr(line) => {
                         let output = String::from_utf8_lossy(&line).trim().to_string();
                         if !output.is_empty() {
-                            process_state.add_log("STDERR", output);
+                            // stderr 上多为错误与未捕获异常的堆栈，暂停记录时也保留
+                            process_state.add_log("STDERR", output.clone());
+                            process_state.add_known_error_hint(&output);
                         }
                     }
                     CommandEvent::Error(e) => {
@@ -314,12 +594,9 @@ pub async fn start_verdaccio(
                             "INFO",
                             format!("Verdaccio 进程已退出, 退出码: {:?}", payload.code),
                         );
-                        process_state.set_running(false);
                         if let Ok(mut child) = process_state.child.lock() {
                             *child = None;
-                        }
-                        if let Ok(mut pid) = process_state.pid.lock() {
-                            *pid = None;
+                            process_state.clear_process_state();
                         }
                         break;
                     }
@@ -333,34 +610,44 @@ pub async fn start_verdaccio(
         running: VerdaccioRunningState::Starting,
         port,
         pid: Some(pid),
-        storage_path: get_storage_path().to_string_lossy().to_string(),
+        storage_path: get_storage_path()?.to_string_lossy().to_string(),
         config_path: config_path.to_string_lossy().to_string(),
     })
 }
 
 /// 停止 Verdaccio 服务
+///
+/// 返回是否确实结束了一个正在运行的进程；进程已自行退出时返回 false
 #[tauri::command]
-pub async fn stop_verdaccio(process: State<'_, VerdaccioProcess>) -> Result<(), String> {
+pub async fn stop_verdaccio(process: State<'_, VerdaccioProcess>) -> Result<bool, String> {
+    if process.attached.load(Ordering::Relaxed) {
+        return Err("当前 Verdaccio 不是由本应用启动的，无法停止；如需断开请使用 detach_from_external".to_string());
+    }
+
     process.add_log("INFO", "正在停止 Verdaccio...".to_string());
 
+    // 持有 child 锁直到状态清理完成，避免与进程退出回调交错
     let mut child = process.child.lock().map_err(|e| e.to_string())?;
 
-    if let Some(proc) = child.take() {
-        proc.kill().map_err(|e| {
-            let msg = format!("停止进程失败: {}", e);
-            process.add_log("ERROR", msg.clone());
-            msg
-        })?;
-        process.add_log("INFO", "Verdaccio 已停止".to_string());
-    }
+    let killed = match child.take() {
+        Some(proc) => {
+            proc.kill().map_err(|e| {
+                let msg = format!("停止进程失败: {}", e);
+                process.add_log("ERROR", msg.clone());
+                msg
+            })?;
+            process.add_log("INFO", "Verdaccio 已停止".to_string());
+            true
+        }
+        None => {
+            process.add_log("INFO", "Verdaccio 已经停止".to_string());
+            false
+        }
+    };
 
-    {
-        let mut pid = process.pid.lock().map_err(|e| e.to_string())?;
-        *pid = None;
-    }
-    process.set_running(false);
+    process.clear_process_state();
 
-    Ok(())
+    Ok(killed)
 }
 
 /// 检查 Verdaccio API 是否就绪
@@ -379,11 +666,118 @@ async fn check_api_ready(port: u16) -> bool {
     }
 }
 
+/// 确认端口上运行的确实是 Verdaccio（而不是任意返回 HTTP 响应的服务）
+///
+/// `/-/ping` 返回成功且 `X-Powered-By` 为 verdaccio，或包列表 API 返回 JSON 数组时才视为 Verdaccio
+async fn is_verdaccio_server(port: u16) -> bool {
+    let Ok(client) = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_millis(500))
+        .build()
+    else {
+        return false;
+    };
+
+    if let Ok(response) = client.get(format!("http://localhost:{}/-/ping", port)).send().await {
+        let powered_by_verdaccio = response
+            .headers()
+            .get("x-powered-by")
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.to_lowercase().contains("verdaccio"));
+        if response.status().is_success() && powered_by_verdaccio {
+            return true;
+        }
+    }
+
+    let url = format!("http://localhost:{}/-/verdaccio/data/packages", port);
+    match client.get(&url).send().await {
+        Ok(response) if response.status().is_success() => response
+            .json::<serde_json::Value>()
+            .await
+            .is_ok_and(|body| body.is_array()),
+        _ => false,
+    }
+}
+
+/// 检测端口上是否有不是由本应用启动的 Verdaccio（如上次运行遗留或作为系统服务运行）
+#[tauri::command]
+pub async fn detect_external_verdaccio(
+    process: State<'_, VerdaccioProcess>,
+    port: u16,
+) -> Result<ExternalVerdaccio, String> {
+    let managed = {
+        let child = process.child.lock().map_err(|e| e.to_string())?;
+        let managed_port = *process.port.lock().map_err(|e| e.to_string())?;
+        child.is_some() && managed_port == port
+    };
+    if managed {
+        return Ok(ExternalVerdaccio {
+            external: false,
+            port_in_use: true,
+        });
+    }
+
+    let port_in_use = std::net::TcpStream::connect_timeout(
+        &std::net::SocketAddr::from((std::net::Ipv4Addr::LOCALHOST, port)),
+        std::time::Duration::from_millis(500),
+    )
+    .is_ok();
+
+    Ok(ExternalVerdaccio {
+        external: port_in_use && is_verdaccio_server(port).await,
+        port_in_use,
+    })
+}
+
+/// 接管端口上外部启动的 Verdaccio，使应用可以显示状态并管理包和用户
+///
+/// 接管后没有子进程输出，日志中只有本应用产生的记录；`stop_verdaccio` 会拒绝停止该进程
+#[tauri::command]
+pub async fn attach_to_external(
+    process: State<'_, VerdaccioProcess>,
+    port: u16,
+) -> Result<VerdaccioStatus, String> {
+    {
+        let child = process.child.lock().map_err(|e| e.to_string())?;
+        if child.is_some() {
+            return Err("已有由本应用启动的 Verdaccio 在运行".to_string());
+        }
+    }
+
+    if !is_verdaccio_server(port).await {
+        return Err(format!("端口 {} 上没有可访问的 Verdaccio", port));
+    }
+
+    {
+        let mut process_port = process.port.lock().map_err(|e| e.to_string())?;
+        *process_port = port;
+        let mut process_pid = process.pid.lock().map_err(|e| e.to_string())?;
+        *process_pid = None;
+    }
+    process.attached.store(true, Ordering::Relaxed);
+    process.set_running(true);
+    process.add_log("INFO", format!("已接管端口 {} 上外部启动的 Verdaccio", port));
+
+    get_verdaccio_status(process).await
+}
+
+/// 断开对外部 Verdaccio 的接管（不会停止该进程）
+#[tauri::command]
+pub async fn detach_from_external(process: State<'_, VerdaccioProcess>) -> Result<(), String> {
+    if !process.attached.swap(false, Ordering::Relaxed) {
+        return Err("当前没有接管外部 Verdaccio".to_string());
+    }
+    process.set_running(false);
+    process.add_log("INFO", "已断开外部 Verdaccio".to_string());
+    Ok(())
+}
+
 /// 获取 Verdaccio 状态
 #[tauri::command]
 pub async fn get_verdaccio_status(
     process: State<'_, VerdaccioProcess>,
 ) -> Result<VerdaccioStatus, String> {
+    process.refresh_attached().await;
+
     let port = *process.port.lock().map_err(|e| e.to_string())?;
     let pid = *process.pid.lock().map_err(|e| e.to_string())?;
     let is_running = process.check_running();
@@ -402,11 +796,157 @@ pub async fn get_verdaccio_status(
         running,
         port,
         pid,
-        storage_path: get_storage_path().to_string_lossy().to_string(),
-        config_path: get_config_path().to_string_lossy().to_string(),
+        storage_path: get_storage_path()?.to_string_lossy().to_string(),
+        config_path: get_config_path()?.to_string_lossy().to_string(),
     })
 }
 
+/// 获取 Verdaccio 进程的 CPU 与内存占用，服务未运行时返回 None
+///
+/// CPU 占用需要两次采样计算，因此本命令会等待一个最小采样间隔
+#[tauri::command]
+pub async fn get_process_stats(
+    process: State<'_, VerdaccioProcess>,
+) -> Result<Option<ProcessStats>, String> {
+    use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+    let Some(pid) = *process.pid.lock().map_err(|e| e.to_string())? else {
+        return Ok(None);
+    };
+    if !process.check_running() {
+        return Ok(None);
+    }
+
+    let pid = Pid::from_u32(pid);
+    let refresh_kind = ProcessRefreshKind::nothing().with_cpu().with_memory();
+    let mut system = System::new();
+
+    system.refresh_processes_specifics(ProcessesToUpdate::Some(&[pid]), true, refresh_kind);
+    tokio::time::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL).await;
+    system.refresh_processes_specifics(ProcessesToUpdate::Some(&[pid]), true, refresh_kind);
+
+    Ok(system.process(pid).map(|p| ProcessStats {
+        cpu_percent: p.cpu_usage(),
+        memory_bytes: p.memory(),
+    }))
+}
+
+/// 判断 Verdaccio 版本是否能处理 SIGHUP 重新加载配置（无法解析的版本视为不支持）
+fn supports_sighup_reload(version: &str) -> bool {
+    let Some(min_version) = SIGHUP_RELOAD_MIN_VERSION.and_then(|v| semver::Version::parse(v).ok()) else {
+        return false;
+    };
+    semver::Version::parse(version).is_ok_and(|v| v >= min_version)
+}
+
+/// 通过 SIGHUP 让 Verdaccio 重新加载配置，无需完整重启
+///
+/// 只有已知能处理 SIGHUP 的 Verdaccio 版本才会发送信号（否则进程会直接退出），其余情况及
+/// Windows 上直接返回需要重启。发送后在超时时间内查找 Verdaccio 输出的重新加载日志判断是否生效，
+/// 未观察到时同样返回需要重启
+#[tauri::command]
+pub async fn reload_verdaccio_config(
+    app: AppHandle,
+    process: State<'_, VerdaccioProcess>,
+) -> Result<ConfigReloadResult, String> {
+    use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, Signal, System};
+
+    let Some(pid) = *process.pid.lock().map_err(|e| e.to_string())? else {
+        return Err("Verdaccio 未运行".to_string());
+    };
+    if !process.check_running() {
+        return Err("Verdaccio 未运行".to_string());
+    }
+
+    let restart_required = ConfigReloadResult {
+        reloaded: false,
+        restart_required: true,
+    };
+
+    let version = get_verdaccio_version(app).await.unwrap_or_default();
+    if !supports_sighup_reload(&version) {
+        process.add_log(
+            "INFO",
+            format!("Verdaccio {} 不支持热重载配置，需要重启 Verdaccio 以应用配置", version),
+        );
+        return Ok(restart_required);
+    }
+
+    let pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        true,
+        ProcessRefreshKind::nothing(),
+    );
+    let Some(target) = system.process(pid) else {
+        return Err("未找到 Verdaccio 进程".to_string());
+    };
+
+    let started_at = chrono::Local::now().format(LOG_TIMESTAMP_FORMAT).to_string();
+    match target.kill_with(Signal::Hangup) {
+        None => {
+            process.add_log("INFO", "当前平台不支持 SIGHUP，需要重启 Verdaccio 以应用配置".to_string());
+            return Ok(restart_required);
+        }
+        Some(false) => {
+            let msg = "发送 SIGHUP 失败".to_string();
+            process.add_log("ERROR", msg.clone());
+            return Err(msg);
+        }
+        Some(true) => {
+            process.add_log("INFO", format!("已向 Verdaccio (PID {}) 发送 SIGHUP", pid));
+        }
+    }
+
+    let deadline = tokio::time::Instant::now() + CONFIG_RELOAD_TIMEOUT;
+    while tokio::time::Instant::now() < deadline {
+        tokio::time::sleep(LOG_BATCH_INTERVAL).await;
+        if !process.check_running() {
+            return Err("Verdaccio 收到 SIGHUP 后已退出，请重新启动".to_string());
+        }
+        // 只认 Verdaccio 自身输出的重新加载日志，避免请求路径等内容误判
+        let reloaded = process
+            .logs
+            .lock()
+            .map(|logs| {
+                logs.iter()
+                    .rev()
+                    .take_while(|e| e.timestamp >= started_at)
+                    .any(|e| e.level == "STDOUT" && e.message.contains(CONFIG_RELOADED_LOG))
+            })
+            .unwrap_or(false);
+        if reloaded {
+            process.add_log("INFO", "Verdaccio 已重新加载配置".to_string());
+            return Ok(ConfigReloadResult {
+                reloaded: true,
+                restart_required: false,
+            });
+        }
+    }
+
+    process.add_log("WARN", "未检测到配置重新加载，需要重启 Verdaccio 以应用配置".to_string());
+    Ok(restart_required)
+}
+
+/// 启动日志批量推送：每个合并窗口内新增的日志合并为一个 `verdaccio-logs-batch` 事件，
+/// 避免请求高峰时逐条发送事件阻塞 IPC；完整日志仍通过 `get_verdaccio_logs` 获取
+pub fn start_log_batch_emitter(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(LOG_BATCH_INTERVAL).await;
+            let Some(process) = app.try_state::<VerdaccioProcess>() else {
+                break;
+            };
+            let batch = match process.pending_logs.lock() {
+                Ok(mut pending) if !pending.is_empty() => std::mem::take(&mut *pending),
+                _ => continue,
+            };
+            let _ = app.emit("verdaccio-logs-batch", batch);
+        }
+    });
+}
+
 /// 获取服务日志
 #[tauri::command]
 pub async fn get_verdaccio_logs(
@@ -416,6 +956,142 @@ pub async fn get_verdaccio_logs(
     Ok(logs.iter().cloned().collect())
 }
 
+/// 获取指定时间之后的新日志，供前端增量轮询（时间格式与日志条目的 `timestamp` 相同）
+///
+/// 只返回严格晚于该时间的条目；同一毫秒内的多条日志可能在两次轮询之间被跳过
+#[tauri::command]
+pub async fn get_logs_since(
+    process: State<'_, VerdaccioProcess>,
+    timestamp: String,
+) -> Result<Vec<LogEntry>, String> {
+    let parse = |ts: &str| chrono::NaiveDateTime::parse_from_str(ts, LOG_TIMESTAMP_FORMAT);
+    let since = parse(&timestamp).map_err(|e| format!("无效的时间 {}: {}", timestamp, e))?;
+
+    let logs = process.logs.lock().map_err(|e| e.to_string())?;
+    let mut entries: Vec<LogEntry> = logs
+        .iter()
+        .rev()
+        .take_while(|entry| parse(&entry.timestamp).is_ok_and(|time| time > since))
+        .cloned()
+        .collect();
+    entries.reverse();
+
+    Ok(entries)
+}
+
+/// 判断 Verdaccio 输出的日志行是否为 error / fatal 级别（支持 pretty 与 JSON 两种日志格式）
+fn is_error_log_line(output: &str) -> bool {
+    if let Ok(json) = serde_json::from_str::<serde_json::Value>(output) {
+        // pino 日志级别：50 为 error，60 为 fatal
+        return json.get("level").and_then(|l| l.as_u64()).is_some_and(|l| l >= 50);
+    }
+    // pretty 格式的级别位于行首（pretty-timestamped 格式前面还有时间戳）
+    static PRETTY_LEVEL_RE: LazyLock<regex::Regex> = LazyLock::new(|| {
+        regex::Regex::new(r"(?i)^(?:[\[(][^\])]*[\])]\s*)?(error|fatal)\s*-{3}").unwrap()
+    });
+    PRETTY_LEVEL_RE.is_match(&VerdaccioProcess::strip_ansi_codes(output))
+}
+
+/// 暂停或恢复记录 Verdaccio 输出（暂停期间仍会记录 stderr 与 error / fatal 级别的日志）
+#[tauri::command]
+pub async fn set_log_capture(process: State<'_, VerdaccioProcess>, enabled: bool) -> Result<(), String> {
+    process.log_capture.store(enabled, Ordering::Relaxed);
+    Ok(())
+}
+
+/// 获取各级别日志数量（键 `total` 为日志总数），供状态徽标轮询使用
+#[tauri::command]
+pub async fn get_log_level_counts(
+    process: State<'_, VerdaccioProcess>,
+) -> Result<HashMap<String, usize>, String> {
+    let logs = process.logs.lock().map_err(|e| e.to_string())?;
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for entry in logs.iter() {
+        *counts.entry(entry.level.clone()).or_default() += 1;
+    }
+    counts.insert("total".to_string(), logs.len());
+
+    Ok(counts)
+}
+
+/// 解析 pretty 格式的 http 响应日志，如
+/// `http <-- 200, user: null(127.0.0.1), req: 'GET /react', bytes: 0/4035`
+fn parse_pretty_request(message: &str, timestamp: &str) -> Option<RequestEntry> {
+    static REQUEST_RE: LazyLock<regex::Regex> = LazyLock::new(|| {
+        regex::Regex::new(r"<--\s+(\d{3}),\s+user:\s+[^(]*\(([^)]*)\),\s+req:\s+'(\S+)\s+([^']+)'")
+            .unwrap()
+    });
+    let caps = REQUEST_RE.captures(message)?;
+
+    Some(RequestEntry {
+        status: caps[1].parse().ok()?,
+        remote_addr: Some(caps[2].to_string()).filter(|a| !a.is_empty()),
+        method: caps[3].to_string(),
+        path: caps[4].to_string(),
+        time: timestamp.to_string(),
+    })
+}
+
+/// 解析 JSON 格式的 http 响应日志（包含 request / status 字段的行）
+fn parse_json_request(message: &str, timestamp: &str) -> Option<RequestEntry> {
+    let json: serde_json::Value = serde_json::from_str(message).ok()?;
+    let request = json.get("request")?;
+    let status = json.get("status")?.as_u64().and_then(|s| u16::try_from(s).ok())?;
+
+    let time = json
+        .get("time")
+        .and_then(|t| t.as_i64())
+        .and_then(chrono::DateTime::from_timestamp_millis)
+        .map(|t| {
+            t.with_timezone(&chrono::Local)
+                .format(LOG_TIMESTAMP_FORMAT)
+                .to_string()
+        })
+        .unwrap_or_else(|| timestamp.to_string());
+
+    Some(RequestEntry {
+        method: request.get("method")?.as_str()?.to_string(),
+        path: request.get("url")?.as_str()?.to_string(),
+        status,
+        remote_addr: json
+            .get("remoteIP")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string()),
+        time,
+    })
+}
+
+/// 从已捕获的日志中提取最近的请求记录（新的在前），支持 pretty 与 JSON 两种日志格式
+#[tauri::command]
+pub async fn get_recent_requests(
+    process: State<'_, VerdaccioProcess>,
+    count: usize,
+) -> Result<Vec<RequestEntry>, String> {
+    // 先复制进程输出再解析，避免解析期间一直持有日志锁阻塞 add_log
+    let output: Vec<LogEntry> = process
+        .logs
+        .lock()
+        .map_err(|e| e.to_string())?
+        .iter()
+        .filter(|entry| entry.level == "STDOUT" || entry.level == "STDERR")
+        .cloned()
+        .collect();
+
+    Ok(output
+        .iter()
+        .rev()
+        .filter_map(|entry| {
+            if entry.message.starts_with('{') {
+                parse_json_request(&entry.message, &entry.timestamp)
+            } else {
+                parse_pretty_request(&entry.message, &entry.timestamp)
+            }
+        })
+        .take(count)
+        .collect())
+}
+
 /// 清除服务日志
 #[tauri::command]
 pub async fn clear_verdaccio_logs(process: State<'_, VerdaccioProcess>) -> Result<(), String> {
@@ -424,12 +1100,158 @@ pub async fn clear_verdaccio_logs(process: State<'_, VerdaccioProcess>) -> Resul
     Ok(())
 }
 
+/// 读取配置中的文件日志路径（`log.type: file`），相对路径按配置文件所在目录解析
+fn configured_log_file() -> Result<PathBuf, String> {
+    let config = load_config()?;
+    let log = config.get("log");
+    let log_type = log.and_then(|l| l.get("type")).and_then(|t| t.as_str()).unwrap_or("stdout");
+    if log_type != "file" {
+        return Err(format!("当前日志输出到 {}，仅 file 类型的日志可以轮转", log_type));
+    }
+
+    let path = log
+        .and_then(|l| l.get("path"))
+        .and_then(|p| p.as_str())
+        .filter(|p| !p.is_empty())
+        .ok_or_else(|| "配置中未设置 log.path".to_string())?;
+    let path = PathBuf::from(path);
+    if path.is_absolute() {
+        return Ok(path);
+    }
+
+    let config_path = get_config_path()?;
+    let config_dir = config_path.parent().ok_or_else(|| "无效的配置文件路径".to_string())?;
+    Ok(config_dir.join(path))
+}
+
+/// 轮转 Verdaccio 的文件日志：将当前日志复制为带时间戳的文件后清空原文件，返回归档文件路径
+///
+/// 服务以追加方式持续写入原文件，因此采用复制后清空而非重命名，无需停止服务
+#[tauri::command]
+pub async fn rotate_verdaccio_log() -> Result<String, String> {
+    let log_path = configured_log_file()?;
+    if !log_path.is_file() {
+        return Err(format!("日志文件不存在: {}", log_path.display()));
+    }
+
+    let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let file_name = log_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| "无效的日志文件路径".to_string())?;
+    let rotated_path = log_path.with_file_name(format!("{}.{}", file_name, timestamp));
+
+    std::fs::copy(&log_path, &rotated_path).map_err(|e| format!("归档日志文件失败: {}", e))?;
+    std::fs::OpenOptions::new()
+        .write(true)
+        .truncate(true)
+        .open(&log_path)
+        .map_err(|e| format!("清空日志文件失败: {}", e))?;
+
+    Ok(rotated_path.to_string_lossy().to_string())
+}
+
 /// 检查 Verdaccio 是否就绪
 #[tauri::command]
 pub async fn check_verdaccio_installed() -> Result<bool, String> {
     Ok(true)
 }
 
+/// 判断 Node.js sidecar 是否存在（Tauri 会将 externalBin 放在主程序同目录下）
+fn node_sidecar_exists() -> bool {
+    node_sidecar_path().is_some_and(|path| path.exists())
+}
+
+/// 获取本机的局域网 IP（通过 UDP 套接字选择默认出口网卡，不会实际发送数据）
+fn detect_lan_ip() -> Option<std::net::IpAddr> {
+    let socket = std::net::UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("8.8.8.8:80").ok()?;
+    socket
+        .local_addr()
+        .ok()
+        .map(|addr| addr.ip())
+        .filter(|ip| !ip.is_loopback() && !ip.is_unspecified())
+}
+
+/// 生成仓库地址的二维码（SVG），便于其他设备扫码获取地址
+///
+/// 设置了监听地址时使用该地址，开启局域网访问时使用检测到的局域网 IP，否则使用 localhost
+#[tauri::command]
+pub async fn get_registry_qr(port: u16) -> Result<String, String> {
+    let settings = load_app_settings()?;
+
+    let listen_ip = resolve_listen_ip(settings.allow_lan, settings.listen_address.as_deref())?;
+    let host = if !listen_ip.is_unspecified() && !listen_ip.is_loopback() {
+        listen_ip.to_string()
+    } else if settings.allow_lan {
+        detect_lan_ip()
+            .ok_or_else(|| "获取局域网 IP 失败".to_string())?
+            .to_string()
+    } else {
+        "localhost".to_string()
+    };
+    let host = if host.contains(':') { format!("[{}]", host) } else { host };
+    let url = format!("http://{}:{}/", host, port);
+
+    let code = qrcode::QrCode::new(url.as_bytes()).map_err(|e| format!("生成二维码失败: {}", e))?;
+    Ok(code
+        .render::<qrcode::render::svg::Color>()
+        .min_dimensions(200, 200)
+        .build())
+}
+
+/// 获取 Node.js sidecar 路径
+fn node_sidecar_path() -> Option<PathBuf> {
+    let file_name = if cfg!(windows) { "node.exe" } else { "node" };
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(file_name)))
+}
+
+/// 为命令行显示给参数加引号（包含空白或引号时）
+fn quote_arg(arg: &str) -> String {
+    if arg.is_empty() || arg.contains(|c: char| c.is_whitespace() || c == '"') {
+        format!("\"{}\"", arg.replace('"', "\\\""))
+    } else {
+        arg.to_string()
+    }
+}
+
+/// 获取完整的 Verdaccio 启动命令（不启动进程），便于在终端中复现启动并查看原始错误
+#[tauri::command]
+pub async fn get_launch_command(
+    app: AppHandle,
+    port: u16,
+    allow_lan: bool,
+    listen_address: Option<String>,
+) -> Result<String, String> {
+    let listen_ip = resolve_listen_ip(allow_lan, listen_address.as_deref())?;
+    let node_args = load_app_settings()?.node_args;
+    validate_node_args(&node_args)?;
+    let node_path = node_sidecar_path().ok_or_else(|| "获取 Node.js 路径失败".to_string())?;
+    let verdaccio_entry = get_verdaccio_entry(&app)?;
+
+    let command = std::iter::once(node_path.to_string_lossy().to_string())
+        .chain(launch_args(&node_args, &verdaccio_entry, &get_config_path()?, listen_ip, port))
+        .map(|arg| quote_arg(&arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Ok(command)
+}
+
+/// 获取首次运行所需环境的就绪情况，供前端展示安装检查清单
+#[tauri::command]
+pub async fn get_setup_status(app: AppHandle) -> Result<SetupStatus, String> {
+    Ok(SetupStatus {
+        node_sidecar: node_sidecar_exists(),
+        verdaccio_entry: get_verdaccio_entry(&app).is_ok(),
+        config_exists: get_config_path()?.exists(),
+        storage_exists: get_storage_path()?.exists(),
+        htpasswd_exists: get_htpasswd_path()?.exists(),
+    })
+}
+
 /// 获取 Verdaccio package.json 路径
 fn get_verdaccio_package_json(app: &AppHandle) -> Result<PathBuf, String> {
     // 获取资源目录
@@ -503,12 +1325,98 @@ pub async fn get_verdaccio_version(app: AppHandle) -> Result<String, String> {
     Ok(format!("{}", version))
 }
 
+/// 从 `X-Powered-By: verdaccio/5.x.x` 响应头中解析版本
+fn parse_powered_by_version(header: &str) -> Option<String> {
+    header
+        .split_whitespace()
+        .find_map(|part| part.strip_prefix("verdaccio/"))
+        .map(|v| v.to_string())
+}
+
+/// 从 Web UI 页面注入的配置中解析版本（`"version":"5.x.x"`）
+fn parse_web_ui_version(html: &str) -> Option<String> {
+    static VERSION_RE: LazyLock<regex::Regex> =
+        LazyLock::new(|| regex::Regex::new(r#""version"\s*:\s*"([^"]+)""#).unwrap());
+    VERSION_RE
+        .captures(html)
+        .map(|caps| caps[1].to_string())
+}
+
+/// 获取内置 Verdaccio 版本与正在运行的服务版本，便于发现两者不一致
+///
+/// 运行版本优先从响应头 `X-Powered-By` 读取，读取不到时再从 Web UI 页面解析
+#[tauri::command]
+pub async fn get_running_verdaccio_version(app: AppHandle, port: u16) -> Result<VerdaccioVersions, String> {
+    let bundled = get_verdaccio_version(app).await.ok();
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(3))
+        .build()
+        .map_err(|e| format!("创建 HTTP 客户端失败: {}", e))?;
+
+    let mut running = None;
+    if let Ok(response) = client.get(format!("http://localhost:{}/-/ping", port)).send().await {
+        running = response
+            .headers()
+            .get("x-powered-by")
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_powered_by_version);
+    }
+    if running.is_none() {
+        if let Ok(response) = client.get(format!("http://localhost:{}/", port)).send().await {
+            if let Ok(html) = response.text().await {
+                running = parse_web_ui_version(&html);
+            }
+        }
+    }
+
+    let mismatch = matches!((&bundled, &running), (Some(b), Some(r)) if b != r);
+
+    Ok(VerdaccioVersions {
+        bundled,
+        running,
+        mismatch,
+    })
+}
+
+/// 获取 Node.js sidecar 的版本（首次调用时运行 `node --version`，之后使用缓存）
+#[tauri::command]
+pub async fn get_node_version(
+    app: AppHandle,
+    process: State<'_, VerdaccioProcess>,
+) -> Result<String, String> {
+    if let Some(version) = process.node_version.lock().map_err(|e| e.to_string())?.clone() {
+        return Ok(version);
+    }
+
+    let output = app
+        .shell()
+        .sidecar("node")
+        .map_err(|e| format!("创建 Node.js sidecar 失败: {}", e))?
+        .args(["--version"])
+        .output()
+        .await
+        .map_err(|e| format!("运行 Node.js 失败: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "获取 Node.js 版本失败: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    *process.node_version.lock().map_err(|e| e.to_string())? = Some(version.clone());
+
+    Ok(version)
+}
+
 // ========== 配置相关命令 ==========
 
 /// 读取 Verdaccio 配置
 #[tauri::command]
 pub async fn get_verdaccio_config() -> Result<String, String> {
-    let config_path = get_config_path();
+    let config_path = get_config_path()?;
 
     if !config_path.exists() {
         return Err("配置文件不存在".to_string());
@@ -517,26 +1425,23 @@ pub async fn get_verdaccio_config() -> Result<String, String> {
     std::fs::read_to_string(&config_path).map_err(|e| format!("读取配置文件失败: {}", e))
 }
 
-/// 保存 Verdaccio 配置
+/// 保存 Verdaccio 配置，返回保存后的配置哈希（与 `get_config_hash` 一致）
 #[tauri::command]
-pub async fn save_verdaccio_config(config: String) -> Result<(), String> {
-    let config_path = get_config_path();
+pub async fn save_verdaccio_config(config: String) -> Result<String, String> {
+    let config_path = get_config_path()?;
 
-    std::fs::write(&config_path, config).map_err(|e| format!("保存配置文件失败: {}", e))
+    write_config_file(&config_path, &config).map_err(|e| format!("保存配置文件失败: {}", e))?;
+    Ok(config_hash(config.as_bytes()))
 }
 
 /// 获取配置文件路径
 #[tauri::command]
 pub async fn get_config_file_path() -> Result<String, String> {
-    Ok(get_config_path().to_string_lossy().to_string())
+    Ok(get_config_path()?.to_string_lossy().to_string())
 }
 
-/// 重置为默认配置
-#[tauri::command]
-pub async fn reset_config_to_default() -> Result<(), String> {
-    let config_path = get_config_path();
-
-    let default_config = r#"# Verdaccio 配置文件
+/// 默认配置（代理 npmjs）
+pub(crate) const DEFAULT_CONFIG: &str = r#"# Verdaccio 配置文件
 storage: ./storage
 auth:
   htpasswd:
@@ -569,5 +1474,176 @@ log:
   level: http
 "#;
 
-    std::fs::write(&config_path, default_config).map_err(|e| format!("重置配置文件失败: {}", e))
+/// 离线配置：不使用上游仓库，所有人都可以在本地发布
+const OFFLINE_CONFIG: &str = r#"# Verdaccio 配置文件（离线）
+storage: ./storage
+auth:
+  htpasswd:
+    file: ./htpasswd
+    max_users: -1
+packages:
+  '@*/*':
+    access: $all
+    publish: $all
+  '**':
+    access: $all
+    publish: $all
+server:
+  keepAliveTimeout: 60
+log:
+  type: stdout
+  format: pretty
+  level: http
+"#;
+
+/// 仅私有包配置：不代理上游，访问与发布都需要登录
+const PRIVATE_ONLY_CONFIG: &str = r#"# Verdaccio 配置文件（仅私有包）
+storage: ./storage
+auth:
+  htpasswd:
+    file: ./htpasswd
+    max_users: -1
+packages:
+  '@*/*':
+    access: $authenticated
+    publish: $authenticated
+    unpublish: $authenticated
+  '**':
+    access: $authenticated
+    publish: $authenticated
+    unpublish: $authenticated
+server:
+  keepAliveTimeout: 60
+log:
+  type: stdout
+  format: pretty
+  level: http
+"#;
+
+/// 镜像配置：以缓存上游为主，延长元数据缓存时间并容忍上游短暂故障
+const MIRROR_CONFIG: &str = r#"# Verdaccio 配置文件（npm 镜像）
+storage: ./storage
+auth:
+  htpasswd:
+    file: ./htpasswd
+    max_users: -1
+uplinks:
+  npmjs:
+    url: https://registry.npmjs.org/
+    cache: true
+    maxage: 30m
+    timeout: 60s
+    max_fails: 5
+    fail_timeout: 5m
+packages:
+  '@*/*':
+    access: $all
+    publish: $authenticated
+    proxy: npmjs
+  '**':
+    access: $all
+    publish: $authenticated
+    proxy: npmjs
+server:
+  keepAliveTimeout: 60
+middlewares:
+  audit:
+    enabled: true
+log:
+  type: stdout
+  format: pretty
+  level: warn
+"#;
+
+/// 配置模板
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigTemplate {
+    /// 代理 npmjs（默认配置）
+    ProxyNpm,
+    /// 离线使用，不连接上游
+    Offline,
+    /// 仅私有包，需要登录访问
+    PrivateOnly,
+    /// npm 镜像缓存
+    Mirror,
+}
+
+/// 配置差异行的类型
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffTag {
+    Equal,
+    /// 仅存在于默认配置中
+    Insert,
+    /// 仅存在于当前配置中（重置后会丢失）
+    Delete,
+}
+
+/// 配置差异行
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigDiffLine {
+    pub tag: DiffTag,
+    /// 在当前配置中的行号（从 1 开始）
+    pub old_line: Option<usize>,
+    /// 在默认配置中的行号（从 1 开始）
+    pub new_line: Option<usize>,
+    pub content: String,
+}
+
+/// 比较当前配置与默认配置，供重置前确认会丢失哪些内容
+#[tauri::command]
+pub async fn diff_config_against_default() -> Result<Vec<ConfigDiffLine>, String> {
+    let config_path = get_config_path()?;
+    let current = if config_path.exists() {
+        std::fs::read_to_string(&config_path).map_err(|e| format!("读取配置文件失败: {}", e))?
+    } else {
+        String::new()
+    };
+
+    let diff = similar::TextDiff::from_lines(current.as_str(), DEFAULT_CONFIG);
+    Ok(diff
+        .iter_all_changes()
+        .map(|change| ConfigDiffLine {
+            tag: match change.tag() {
+                similar::ChangeTag::Equal => DiffTag::Equal,
+                similar::ChangeTag::Insert => DiffTag::Insert,
+                similar::ChangeTag::Delete => DiffTag::Delete,
+            },
+            old_line: change.old_index().map(|i| i + 1),
+            new_line: change.new_index().map(|i| i + 1),
+            content: change.value().trim_end_matches(['\r', '\n']).to_string(),
+        })
+        .collect())
+}
+
+/// 重置为默认配置
+#[tauri::command]
+pub async fn reset_config_to_default() -> Result<(), String> {
+    let config_path = get_config_path()?;
+
+    write_config_file(&config_path, DEFAULT_CONFIG).map_err(|e| format!("重置配置文件失败: {}", e))
+}
+
+/// 应用配置模板（应用前会备份当前配置），返回备份文件路径
+#[tauri::command]
+pub async fn apply_config_template(template: ConfigTemplate) -> Result<Option<String>, String> {
+    let config_path = get_config_path()?;
+
+    let backup_path = if config_path.exists() {
+        Some(backup_config()?.to_string_lossy().to_string())
+    } else {
+        None
+    };
+
+    let content = match template {
+        ConfigTemplate::ProxyNpm => DEFAULT_CONFIG,
+        ConfigTemplate::Offline => OFFLINE_CONFIG,
+        ConfigTemplate::PrivateOnly => PRIVATE_ONLY_CONFIG,
+        ConfigTemplate::Mirror => MIRROR_CONFIG,
+    };
+
+    write_config_file(&config_path, content).map_err(|e| format!("应用配置模板失败: {}", e))?;
+
+    Ok(backup_path)
 }
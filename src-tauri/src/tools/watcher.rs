@@ -0,0 +1,163 @@
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::config::{config_hash, is_own_config_write};
+use super::packages::{get_storage_path, PrivatePackageCache};
+use super::users::get_htpasswd_path;
+use super::verdaccio::get_config_path;
+
+/// 存储目录变更的防抖时间，避免发布过程中频繁刷新
+const STORAGE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// 配置文件变更的防抖时间，编辑器保存时通常会产生多个事件
+const CONFIG_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// 文件监听器（持有期间保持监听，释放即停止）
+#[derive(Default)]
+pub struct FileWatchers {
+    users: Mutex<Option<RecommendedWatcher>>,
+    storage: Mutex<Option<Debouncer<RecommendedWatcher>>>,
+    config: Mutex<Option<Debouncer<RecommendedWatcher>>>,
+}
+
+/// 判断事件是否为内容变更（忽略仅访问类事件）
+fn is_change_event(event: &Event) -> bool {
+    !matches!(event.kind, EventKind::Access(_))
+}
+
+/// 启动 htpasswd 监听，文件被外部修改（如 `npm adduser`）时发送 `users-changed` 事件
+///
+/// 写入 htpasswd 时使用重命名替换，直接监听文件会在替换后失效，因此改为监听所在目录
+pub fn start_users_watcher(app: &AppHandle) -> Result<(), String> {
+    let htpasswd_path = get_htpasswd_path()?;
+    let watch_dir = htpasswd_path
+        .parent()
+        .ok_or_else(|| "无效的 htpasswd 文件路径".to_string())?
+        .to_path_buf();
+    let file_name = htpasswd_path.file_name().map(|n| n.to_os_string());
+
+    std::fs::create_dir_all(&watch_dir).map_err(|e| format!("创建目录失败: {}", e))?;
+
+    let app_handle = app.clone();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+        let Ok(event) = res else {
+            return;
+        };
+        if is_change_event(&event)
+            && event
+                .paths
+                .iter()
+                .any(|p| p.file_name().map(|n| n.to_os_string()) == file_name)
+        {
+            let _ = app_handle.emit("users-changed", ());
+        }
+    })
+    .map_err(|e| format!("创建文件监听失败: {}", e))?;
+
+    watcher
+        .watch(&watch_dir, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("监听 htpasswd 失败: {}", e))?;
+
+    let watchers = app.state::<FileWatchers>();
+    let mut users = watchers.users.lock().map_err(|e| e.to_string())?;
+    *users = Some(watcher);
+
+    Ok(())
+}
+
+/// 启动存储目录监听，包被发布或删除时清除私有包名称缓存并发送 `packages-changed` 事件
+///
+/// 递归监听以覆盖 `@scope/` 下的 scoped 包目录，并做防抖合并
+pub fn start_storage_watcher(app: &AppHandle) -> Result<(), String> {
+    let storage_path = get_storage_path()?;
+    std::fs::create_dir_all(&storage_path).map_err(|e| format!("创建存储目录失败: {}", e))?;
+
+    let app_handle = app.clone();
+    let mut debouncer = new_debouncer(STORAGE_DEBOUNCE, move |res: DebounceEventResult| {
+        if matches!(res, Ok(events) if !events.is_empty()) {
+            // 包可能是通过 npm 发布的，私有包列表需要重新请求
+            app_handle.state::<PrivatePackageCache>().invalidate();
+            let _ = app_handle.emit("packages-changed", ());
+        }
+    })
+    .map_err(|e| format!("创建文件监听失败: {}", e))?;
+
+    debouncer
+        .watcher()
+        .watch(&storage_path, RecursiveMode::Recursive)
+        .map_err(|e| format!("监听存储目录失败: {}", e))?;
+
+    let watchers = app.state::<FileWatchers>();
+    let mut storage = watchers.storage.lock().map_err(|e| e.to_string())?;
+    *storage = Some(debouncer);
+
+    Ok(())
+}
+
+/// 启动配置文件监听，config.yaml 被修改（如在外部编辑器中保存）时发送 `config-file-changed` 事件
+///
+/// 内容确实发生变化且不是应用自身写入时，还会发送携带新内容哈希的 `config-externally-modified`
+/// 事件，前端应在覆盖前提示重新加载
+pub fn start_config_watcher(app: &AppHandle) -> Result<(), String> {
+    let config_path = get_config_path()?;
+    let watch_dir = config_path
+        .parent()
+        .ok_or_else(|| "无效的配置文件路径".to_string())?
+        .to_path_buf();
+    let file_name = config_path.file_name().map(|n| n.to_os_string());
+
+    std::fs::create_dir_all(&watch_dir).map_err(|e| format!("创建目录失败: {}", e))?;
+
+    let app_handle = app.clone();
+    let mut last_hash = std::fs::read(&config_path).ok().map(|content| config_hash(&content));
+    let mut debouncer = new_debouncer(CONFIG_DEBOUNCE, move |res: DebounceEventResult| {
+        let Ok(events) = res else {
+            return;
+        };
+        if events
+            .iter()
+            .any(|e| e.path.file_name().map(|n| n.to_os_string()) == file_name)
+        {
+            let _ = app_handle.emit("config-file-changed", ());
+
+            // 编辑器保存时可能产生内容未变的事件，只在哈希变化时通知；应用自身保存的内容不算外部修改
+            let hash = std::fs::read(&config_path).ok().map(|content| config_hash(&content));
+            if let Some(hash) = hash.filter(|hash| last_hash.as_ref() != Some(hash)) {
+                if !is_own_config_write(&hash) {
+                    let _ = app_handle.emit("config-externally-modified", &hash);
+                }
+                last_hash = Some(hash);
+            }
+        }
+    })
+    .map_err(|e| format!("创建文件监听失败: {}", e))?;
+
+    debouncer
+        .watcher()
+        .watch(&watch_dir, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("监听配置文件失败: {}", e))?;
+
+    let watchers = app.state::<FileWatchers>();
+    let mut config = watchers.config.lock().map_err(|e| e.to_string())?;
+    *config = Some(debouncer);
+
+    Ok(())
+}
+
+/// 停止所有文件监听
+pub fn stop_watchers(app: &AppHandle) {
+    if let Some(watchers) = app.try_state::<FileWatchers>() {
+        if let Ok(mut users) = watchers.users.lock() {
+            users.take();
+        }
+        if let Ok(mut storage) = watchers.storage.lock() {
+            storage.take();
+        }
+        if let Ok(mut config) = watchers.config.lock() {
+            config.take();
+        }
+    }
+}
@@ -1,12 +1,16 @@
 import { invoke } from '@tauri-apps/api/core'
-import type { AppSettings, LogEntry, PackageInfo, PackageType, PaginatedResult, UserInfo, VerdaccioRunningStatus, VerdaccioStatus } from '../types'
+import type { AppSettings, AutostartCheck, LogEntry, PackageInfo, PackageType, PaginatedResult, UserInfo, VerdaccioRunningStatus, VerdaccioStatus } from '../types'
 
 // Verdaccio 服务相关
-export async function startVerdaccio(port: number, allowLan: boolean): Promise<VerdaccioStatus> {
-  return invoke('start_verdaccio', { port, allowLan })
+export async function startVerdaccio(
+  port: number,
+  allowLan: boolean,
+  listenAddress: string | null = null
+): Promise<VerdaccioStatus> {
+  return invoke('start_verdaccio', { port, allowLan, listenAddress })
 }
 
-export async function stopVerdaccio(): Promise<void> {
+export async function stopVerdaccio(): Promise<boolean> {
   return invoke('stop_verdaccio')
 }
 
@@ -27,6 +31,10 @@ export async function getVerdaccioLogs(): Promise<LogEntry[]> {
   return invoke('get_verdaccio_logs')
 }
 
+export async function getLogsSince(timestamp: string): Promise<LogEntry[]> {
+  return invoke('get_logs_since', { timestamp })
+}
+
 export async function clearVerdaccioLogs(): Promise<void> {
   return invoke('clear_verdaccio_logs')
 }
@@ -36,7 +44,7 @@ export async function getVerdaccioConfig(): Promise<string> {
   return invoke('get_verdaccio_config')
 }
 
-export async function saveVerdaccioConfig(config: string): Promise<void> {
+export async function saveVerdaccioConfig(config: string): Promise<string> {
   return invoke('save_verdaccio_config', { config })
 }
 
@@ -48,18 +56,27 @@ export async function resetConfigToDefault(): Promise<void> {
   return invoke('reset_config_to_default')
 }
 
+export async function takeConfigCommentsBackup(): Promise<string | null> {
+  return invoke('take_config_comments_backup')
+}
+
 // 包管理相关
 export async function getPackages(
   port: number,
   packageType: PackageType,
   page: number,
-  pageSize: number
+  pageSize: number,
+  includeHidden = false
 ): Promise<PaginatedResult<PackageInfo>> {
-  return invoke('get_packages', { port, packageType, page, pageSize })
+  return invoke('get_packages', { port, packageType, page, pageSize, includeHidden })
 }
 
-export async function getPackageCount(port: number, packageType: PackageType): Promise<number> {
-  return invoke('get_package_count', { port, packageType })
+export async function getPackageCount(
+  port: number,
+  packageType: PackageType,
+  includeHidden = false
+): Promise<number> {
+  return invoke('get_package_count', { port, packageType, includeHidden })
 }
 
 export async function deletePackage(packageName: string): Promise<void> {
@@ -75,7 +92,7 @@ export async function getAppSettings(): Promise<AppSettings> {
   return invoke('get_app_settings')
 }
 
-export async function saveAppSettings(settings: AppSettings): Promise<void> {
+export async function saveAppSettings(settings: Partial<AppSettings>): Promise<void> {
   return invoke('save_app_settings', { settings })
 }
 
@@ -87,6 +104,10 @@ export async function getAutoStartStatus(): Promise<boolean> {
   return invoke('get_auto_start_status')
 }
 
+export async function ensureAutostartMinimized(): Promise<AutostartCheck> {
+  return invoke('ensure_autostart_minimized')
+}
+
 // 托盘相关
 export async function syncTrayStatus(running: VerdaccioRunningStatus): Promise<void> {
   return invoke('sync_tray_status', { running })
@@ -2,7 +2,7 @@ import { BookOutlined, EditOutlined, FileTextOutlined, ReloadOutlined, SaveOutli
 import { useAsyncEffect, useMemoizedFn } from 'ahooks'
 import { App, Button, Card, Input, Space, Spin, Tabs, Typography } from 'antd'
 import { type FC, useState } from 'react'
-import { getConfigFilePath, getVerdaccioConfig, resetConfigToDefault, saveVerdaccioConfig } from '../lib/api'
+import { getConfigFilePath, getVerdaccioConfig, resetConfigToDefault, saveVerdaccioConfig, takeConfigCommentsBackup } from '../lib/api'
 
 const Content: FC = () => {
   const { message, modal } = App.useApp()
@@ -23,6 +23,11 @@ const Content: FC = () => {
 
       const path = await getConfigFilePath()
       setConfigPath(path)
+
+      const backupPath = await takeConfigCommentsBackup()
+      if (backupPath) {
+        message.warning(`配置已被应用修改，原有注释已移除，修改前的配置已备份到 ${backupPath}`)
+      }
     } catch (e) {
       message.error(`加载配置失败: ${e}`)
     } finally {
@@ -17,6 +17,7 @@ const Content: FC = () => {
   const [cachedPackageCount, setCachedPackageCount] = useState(0)
   const [port, setPort] = useState(4873)
   const [allowLan, setAllowLan] = useState(false)
+  const [listenAddress, setListenAddress] = useState<string | null>(null)
 
   const refreshStatus = useMemoizedFn(async () => {
     try {
@@ -29,6 +30,7 @@ const Content: FC = () => {
       // 加载设置中的配置
       const settings = await getAppSettings()
       setAllowLan(settings.allow_lan)
+      setListenAddress(settings.listen_address)
       // 如果服务运行中，使用当前运行端口；否则使用设置中的端口
       if (st.running !== 'not_running') {
         setPort(st.port)
@@ -71,7 +73,7 @@ const Content: FC = () => {
   const handleStart = useMemoizedFn(async () => {
     setActionLoading(true)
     try {
-      await startVerdaccio(port, allowLan)
+      await startVerdaccio(port, allowLan, listenAddress)
       message.success('Verdaccio 已启动')
       await refreshStatus()
     } catch (e) {
@@ -3,7 +3,7 @@ import { getVersion } from '@tauri-apps/api/app'
 import { useAsyncEffect, useMemoizedFn } from 'ahooks'
 import { App, Button, Card, Descriptions, Divider, Form, InputNumber, Space, Spin, Switch, Tooltip, Typography } from 'antd'
 import { type FC, useState } from 'react'
-import { getAppSettings, getAutoStartStatus, saveAppSettings, setAutoStart } from '../lib/api'
+import { ensureAutostartMinimized, getAppSettings, getAutoStartStatus, saveAppSettings, setAutoStart } from '../lib/api'
 import type { AppSettings } from '../types'
 
 const Content: FC = () => {
@@ -22,6 +22,13 @@ const Content: FC = () => {
 
       const autoStart = await getAutoStartStatus()
       setAutoStartEnabled(autoStart)
+      if (autoStart) {
+        // 确保开机自启时以最小化方式启动
+        const check = await ensureAutostartMinimized()
+        if (check.repaired) {
+          message.info('已修复开机自启参数，开机后将最小化到托盘')
+        }
+      }
 
       const version = await getVersion()
       setAppVersion(version)
@@ -47,8 +47,8 @@ const Content: FC = () => {
         // 先检查 Verdaccio 是否已经在运行
         const status = await getVerdaccioStatus()
         if (status.running === 'not_running') {
-          // 自动启动 Verdaccio（使用设置中的端口和局域网配置）
-          const result = await startVerdaccio(settings.default_port, settings.allow_lan)
+          // 自动启动 Verdaccio（使用设置中的端口、局域网配置和监听地址）
+          const result = await startVerdaccio(settings.default_port, settings.allow_lan, settings.listen_address)
           await syncTrayStatus(result.running)
           console.log('自动启动 Verdaccio 成功')
         }
@@ -42,6 +42,8 @@ export interface PackageInfo {
   repository: string | null
   created: string | null
   modified: string | null
+  time_source: 'document' | 'filesystem'
+  hidden: boolean
 }
 
 // 应用设置
@@ -51,10 +53,38 @@ export interface AppSettings {
   auto_start_verdaccio: boolean
   default_port: number
   allow_lan: boolean
+  listen_address: string | null
+  theme: 'light' | 'dark' | 'system'
+  locale: string
+  last_update_check: string | null
+  http_proxy: string | null
+  https_proxy: string | null
+  no_proxy: string | null
+  node_args: string[]
+  env: Record<string, string>
+  default_page_size: number
+  api_timeout_ms: number
+  verbose_mode: boolean
+  verbose_previous_log_level: string | null
+  tray_tooltip_template: string
+  settings_was_reset?: boolean
 }
 
+// 开机自启参数检查结果
+export interface AutostartCheck {
+  enabled: boolean
+  registered_command: string | null
+  minimized: boolean
+  repaired: boolean
+}
+
+// 密码哈希类型
+export type HashType = 'bcrypt' | 'apr1' | 'sha1' | 'crypt' | 'unknown'
+
 // 用户信息
 export interface UserInfo {
   username: string
   created: string | null
+  disabled: boolean
+  hash_type: HashType
 }